@@ -0,0 +1,125 @@
+//! Times parsing and solving of generated inputs for the days with the heaviest parsed
+//! structures. Run with `cargo bench --bench parsing`. The inputs are generated, so
+//! these bench runs are not recorded in the run history.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+const ITERATIONS: u32 = 50;
+
+/// The day 3 example tiled into a 140 by 140 schematic, the size of a real input.
+fn engine_schematic() -> String {
+    let tile = [
+        "467..114..",
+        "...*......",
+        "..35..633.",
+        "......#...",
+        "617*......",
+        ".....+.58.",
+        "..592.....",
+        "......755.",
+        "...$.*....",
+        ".664.598..",
+    ];
+    let mut lines = vec![];
+    for _ in 0..14 {
+        for row in tile {
+            lines.push(row.repeat(14));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Nine stacks of crates shuffled back and forth between neighbouring stacks.
+fn crate_stacks() -> String {
+    let mut lines = vec![];
+    for height in 0..40 {
+        lines.push(
+            (0..9)
+                .map(|stack| format!("[{}]", (b'A' + ((height + stack) % 26) as u8) as char))
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+    }
+    lines.push(
+        (1..=9)
+            .map(|stack| format!(" {stack} "))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    lines.push(String::new());
+    for round in 0..500 {
+        let from = round % 8 + 1;
+        lines.push(format!("move 3 from {from} to {}", from + 1));
+        lines.push(format!("move 3 from {} to {from}", from + 1));
+    }
+    lines.join("\n")
+}
+
+/// A chain of nodes from `AAA` to `ZZZ` whose other names never end in `A` or `Z`.
+fn node_network() -> String {
+    let letters = (b'B'..=b'Y').map(char::from).collect::<Vec<_>>();
+    let mut names = vec![String::from("AAA")];
+    for index in 0..700 {
+        names.push(
+            [index / 576, index / 24 % 24, index % 24]
+                .iter()
+                .map(|letter| letters[*letter])
+                .collect(),
+        );
+    }
+    names.push(String::from("ZZZ"));
+    let mut lines = vec!["LR".repeat(140), String::new()];
+    for (index, name) in names.iter().enumerate() {
+        let next = &names[(index + 1) % names.len()];
+        lines.push(format!("{name} = ({next}, {next})"));
+    }
+    lines.join("\n")
+}
+
+fn strategy_guide() -> String {
+    (0..2500)
+        .map(|round| {
+            format!(
+                "{} {}",
+                ["A", "B", "C"][round % 3],
+                ["X", "Y", "Z"][round / 3 % 3]
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn time_per_iteration(mut run: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        run();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn bench_day(year: &str, day: &str, puzzle_input: &str) {
+    let puzzle = rust_advent::puzzle(year, day).unwrap();
+    let parse_time = time_per_iteration(|| {
+        black_box(puzzle.parse(black_box(puzzle_input)).unwrap());
+    });
+    let parsed_puzzle = puzzle.parse(puzzle_input).unwrap();
+    let solve_time = time_per_iteration(|| {
+        black_box(parsed_puzzle.solve_both().unwrap());
+    });
+    println!("{year} {day:<5}  parse {parse_time:>12?}  solve {solve_time:>12?}");
+}
+
+fn main() {
+    println!("Bench runs on generated inputs, not recorded in the run history");
+    if cfg!(feature = "y2022") {
+        bench_day("2022", "day2", &strategy_guide());
+        bench_day("2022", "day5", &crate_stacks());
+    }
+    if cfg!(feature = "y2023") {
+        bench_day("2023", "day3", &engine_schematic());
+        bench_day("2023", "day8", &node_network());
+    }
+}
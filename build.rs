@@ -0,0 +1,88 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+
+fn main() {
+    let git_commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .unwrap_or_else(|| String::from("unknown"));
+    println!("cargo:rustc-env=ADVENT_GIT_COMMIT={git_commit}");
+    // Changes with the source, so cached answers never outlive the solvers that produced
+    // them, even between commits, but survives rebuilding the same source.
+    let mut source_files = vec![PathBuf::from("Cargo.toml")];
+    list_files(Path::new("src"), &mut source_files);
+    source_files.sort();
+    let source_hash = source_files
+        .iter()
+        .fold(FNV_OFFSET_BASIS, |hash, source_file| {
+            let contents = fs::read(source_file).unwrap_or_default();
+            hash_bytes(
+                hash_bytes(hash, source_file.to_string_lossy().as_bytes()),
+                &contents,
+            )
+        });
+    println!("cargo:rustc-env=ADVENT_BUILD_ID={source_hash:016x}");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=Cargo.toml");
+
+    #[cfg(feature = "ffi")]
+    generate_c_header();
+}
+
+/// FNV-1a, as `history::hash_text` uses, continued from `hash`.
+fn hash_bytes(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn list_files(directory: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            list_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Generates the header for the C API in `src/ffi.rs` into `OUT_DIR`. The copy checked in
+/// at `include/rust_advent.h`, so C users do not need cbindgen, is compared against it by
+/// `tests/ffi.rs`.
+#[cfg(feature = "ffi")]
+fn generate_c_header() {
+    let config = cbindgen::Config {
+        language: cbindgen::Language::C,
+        include_guard: Some(String::from("RUST_ADVENT_H")),
+        autogen_warning: Some(String::from(
+            "/* Generated from src/ffi.rs by build.rs, do not edit by hand. */",
+        )),
+        enumeration: cbindgen::EnumConfig {
+            rename_variants: cbindgen::RenameRule::QualifiedScreamingSnakeCase,
+            ..Default::default()
+        },
+        documentation_style: cbindgen::DocumentationStyle::C,
+        ..Default::default()
+    };
+    let out_directory = std::env::var("OUT_DIR").expect("Cargo sets OUT_DIR for build scripts");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src("src/ffi.rs")
+        .generate()
+        .expect("Unable to generate the C header")
+        .write_to_file(std::path::Path::new(&out_directory).join("rust_advent.h"));
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+}
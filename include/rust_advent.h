@@ -0,0 +1,73 @@
+#ifndef RUST_ADVENT_H
+#define RUST_ADVENT_H
+
+/* Generated from src/ffi.rs by build.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/*
+ Outcome of a call into the library. Anything other than `ADVENT_STATUS_OK` comes with
+ a message in `AdventAnswers::error`.
+ */
+typedef enum AdventStatus {
+  ADVENT_STATUS_OK = 0,
+  ADVENT_STATUS_INVALID_ARGUMENT = 1,
+  ADVENT_STATUS_YEAR_NOT_IMPLEMENTED = 2,
+  ADVENT_STATUS_YEAR_NOT_BUILT = 3,
+  ADVENT_STATUS_DAY_NOT_IMPLEMENTED = 4,
+  ADVENT_STATUS_INVALID_INPUT = 5,
+  ADVENT_STATUS_SOLVER_FAILED = 6,
+} AdventStatus;
+
+/*
+ Answers to a puzzle, single-line with picture answers decoded to letters. `second` is
+ null for days with only one part, and both are null when `error` is set.
+ */
+typedef struct AdventAnswers {
+  char *first;
+  char *second;
+  char *error;
+} AdventAnswers;
+
+/*
+ Lists the registered puzzles, one `<year> <day>` per line, ordered by year and day.
+ Release the result with `advent_free_string`.
+ */
+char *advent_list_puzzles(void);
+
+/*
+ Parses and solves `input_length` bytes of `input` with the solver registered for
+ `year` (e.g. `"2023"`) and `day` (e.g. `"day9"`), filling in `answers`. The input does
+ not need to be NUL-terminated but must be UTF-8. Release the answers with
+ `advent_free_answers`, whatever the status.
+
+ # Safety
+ `year` and `day` must be null or NUL-terminated strings, `input` must be null or point
+ to at least `input_length` readable bytes and `answers` must be null or writable.
+ */
+enum AdventStatus advent_solve(const char *year,
+                               const char *day,
+                               const uint8_t *input,
+                               uintptr_t input_length,
+                               struct AdventAnswers *answers);
+
+/*
+ Releases a string returned by the library. Null is ignored.
+
+ # Safety
+ `text` must be null or a string returned by the library that has not been freed.
+ */
+void advent_free_string(char *text);
+
+/*
+ Releases the strings held by `answers` and sets them to null, so freeing twice is safe.
+
+ # Safety
+ `answers` must be null or filled in by `advent_solve`.
+ */
+void advent_free_answers(struct AdventAnswers *answers);
+
+#endif  /* RUST_ADVENT_H */
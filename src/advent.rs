@@ -1,13 +1,71 @@
-use std::error::Error;
+use std::{error::Error, fmt::Debug};
 
+use serde::Serialize;
+
+pub mod letters;
+pub mod trace;
+pub mod visualize;
+#[cfg(feature = "y2022")]
 pub mod y2022;
+#[cfg(feature = "y2023")]
 pub mod y2023;
 
+pub use trace::{Trace, TraceLevel};
+pub use visualize::Frame;
+
 pub trait Parse {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>>;
+    /// Parsed days may borrow from `puzzle_input` instead of copying it.
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>>;
+}
+
+/// Lets a boxed `AdventDay` hand its parsed structure to serde without naming its type.
+pub trait ToJson {
+    fn to_json_pretty(&self) -> serde_json::Result<String>;
+}
+
+impl<T: Serialize> ToJson for T {
+    fn to_json_pretty(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
 }
 
-pub trait AdventDay {
-    fn solve_first_puzzle(&self) -> String;
-    fn solve_second_puzzle(&self) -> String;
+pub trait AdventDay: Debug + ToJson {
+    fn solve_first_puzzle(&self, trace: &mut Trace) -> String;
+
+    /// `None` for days with only one puzzle, such as 2022 day25.
+    fn solve_second_puzzle(&self, _trace: &mut Trace) -> Option<String> {
+        None
+    }
+
+    /// Solves both parts from shared intermediate results, for days where part 2 would
+    /// otherwise redo part 1's work. Days that return `None` are solved part by part.
+    fn solve_both_puzzles(
+        &self,
+        _first_trace: &mut Trace,
+        _second_trace: &mut Trace,
+    ) -> Option<(String, String)> {
+        None
+    }
+
+    /// Values the solvers read that can be changed between solves, such as the cube limits
+    /// of 2023 day2, with their current values.
+    fn parameters(&self) -> Vec<(&'static str, u64)> {
+        vec![]
+    }
+
+    /// Changes a value listed by `parameters`.
+    fn set_parameter(&mut self, name: &str, _value: u64) -> Result<(), String> {
+        Err(format!("This day has no parameter named {name}"))
+    }
+
+    fn visualize_first_puzzle(&self) -> Option<Vec<Frame>> {
+        None
+    }
+
+    fn visualize_second_puzzle(&self) -> Option<Vec<Frame>> {
+        None
+    }
 }
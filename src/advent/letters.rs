@@ -0,0 +1,295 @@
+use std::{error::Error, ops::Range};
+
+const SMALL_FONT_HEIGHT: usize = 6;
+const LARGE_FONT_HEIGHT: usize = 10;
+/// Letters start every 5 columns in the 4x6 font and every 8 in the 6x10 font.
+const SMALL_FONT_SPACING: usize = 5;
+const LARGE_FONT_SPACING: usize = 8;
+
+const SMALL_FONT: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE_FONT: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+fn parse_pixels(picture: &str) -> Vec<Vec<bool>> {
+    picture
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().map(|pixel| pixel == '#').collect())
+        .collect()
+}
+
+fn is_column_lit(pixels: &[Vec<bool>], column: usize) -> bool {
+    pixels
+        .iter()
+        .any(|row| row.get(column).copied().unwrap_or(false))
+}
+
+/// The unlit columns either side of a glyph are cropped, so glyphs drawn at different
+/// offsets within their cell compare equal. Empty when every column is unlit.
+fn crop_glyph(pixels: &[Vec<bool>], columns: Range<usize>) -> Vec<Vec<bool>> {
+    let lit_columns = columns
+        .filter(|column| is_column_lit(pixels, *column))
+        .collect::<Vec<_>>();
+    let (Some(first), Some(last)) = (lit_columns.first(), lit_columns.last()) else {
+        return vec![];
+    };
+    pixels
+        .iter()
+        .map(|row| {
+            (*first..=*last)
+                .map(|x| row.get(x).copied().unwrap_or(false))
+                .collect()
+        })
+        .collect()
+}
+
+/// Splits a picture into the fixed-width cells its font is drawn in. Letters such as
+/// `Y` fill their whole cell, so they cannot be told apart by the unlit columns between
+/// them. Unlit cells at the end are padding and are dropped.
+fn split_into_glyphs(pixels: &[Vec<bool>], spacing: usize) -> Vec<Vec<Vec<bool>>> {
+    let width = pixels.iter().map(Vec::len).max().unwrap_or(0);
+    let mut glyphs = (0..width)
+        .step_by(spacing)
+        .map(|start| crop_glyph(pixels, start..(start + spacing).min(width)))
+        .collect::<Vec<_>>();
+    while glyphs.last().is_some_and(Vec::is_empty) {
+        glyphs.pop();
+    }
+    glyphs
+}
+
+fn recognize_glyph(glyph: &[Vec<bool>], font: &[(char, &str)]) -> Option<char> {
+    font.iter()
+        .find(|(_, font_glyph)| {
+            let font_pixels = parse_pixels(font_glyph);
+            let width = font_pixels.iter().map(Vec::len).max().unwrap_or(0);
+            crop_glyph(&font_pixels, 0..width) == glyph
+        })
+        .map(|(letter, _)| *letter)
+}
+
+/// Returns whether an answer is a picture of `#` and `.` that could contain letters.
+pub fn is_letter_picture(answer: &str) -> bool {
+    let pixels = parse_pixels(answer);
+    (pixels.len() == SMALL_FONT_HEIGHT || pixels.len() == LARGE_FONT_HEIGHT)
+        && answer.contains('#')
+        && answer
+            .chars()
+            .all(|pixel| matches!(pixel, '#' | '.' | ' ' | '\n'))
+}
+
+/// Decodes capital letters drawn with the 4x6 or 6x10 Advent of Code glyph fonts.
+pub fn recognize_letters(picture: &str) -> Result<String, Box<dyn Error>> {
+    let pixels = parse_pixels(picture);
+    let (font, spacing) = match pixels.len() {
+        SMALL_FONT_HEIGHT => (SMALL_FONT, SMALL_FONT_SPACING),
+        LARGE_FONT_HEIGHT => (LARGE_FONT, LARGE_FONT_SPACING),
+        _ => return Err("Letter pictures must be 6 or 10 rows tall".into()),
+    };
+    split_into_glyphs(&pixels, spacing)
+        .iter()
+        .enumerate()
+        .map(|(index, glyph)| {
+            recognize_glyph(glyph, font)
+                .ok_or_else(|| format!("Could not recognise letter {}", index + 1).into())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws glyphs side by side, each padded to the font's spacing.
+    fn join_glyphs(glyphs: &[&str], spacing: usize) -> String {
+        let glyph_lines = glyphs
+            .iter()
+            .map(|glyph| glyph.lines().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        (0..glyph_lines[0].len())
+            .map(|row| {
+                glyph_lines
+                    .iter()
+                    .map(|lines| format!("{:.<spacing$}", lines[row]))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn recognize_every_small_letter() -> Result<(), Box<dyn Error>> {
+        let expected = SMALL_FONT
+            .iter()
+            .map(|(letter, _)| letter)
+            .collect::<String>();
+
+        let input = join_glyphs(
+            &SMALL_FONT.iter().map(|(_, g)| *g).collect::<Vec<_>>(),
+            SMALL_FONT_SPACING,
+        );
+        let actual = recognize_letters(&input)?;
+
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+
+    #[test]
+    fn recognize_every_large_letter() -> Result<(), Box<dyn Error>> {
+        let expected = LARGE_FONT
+            .iter()
+            .map(|(letter, _)| letter)
+            .collect::<String>();
+
+        let input = join_glyphs(
+            &LARGE_FONT.iter().map(|(_, g)| *g).collect::<Vec<_>>(),
+            LARGE_FONT_SPACING,
+        );
+        let actual = recognize_letters(&input)?;
+
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+
+    #[test]
+    fn recognize_crt_output() -> Result<(), Box<dyn Error>> {
+        let expected = "EHZ";
+
+        let input = "\
+####.#..#.####.
+#....#..#....#.
+###..####...#..
+#....#..#..#...
+#....#..#.#....
+####.#..#.####.";
+        let actual = recognize_letters(input)?;
+
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+
+    #[test]
+    fn letters_filling_their_cell_are_split_by_spacing() -> Result<(), Box<dyn Error>> {
+        let expected = "YE";
+
+        let input = "\
+#...#####.
+#...##....
+.#.#.###..
+..#..#....
+..#..#....
+..#..####.";
+        let actual = recognize_letters(input)?;
+
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_glyph_is_an_error() {
+        let input = "#\n#\n#\n#\n#\n#";
+
+        assert!(recognize_letters(input).is_err());
+    }
+
+    #[test]
+    fn blank_cell_between_letters_is_an_error() {
+        let input = join_glyphs(
+            &[
+                SMALL_FONT[0].1,
+                "....\n....\n....\n....\n....\n....",
+                SMALL_FONT[1].1,
+            ],
+            SMALL_FONT_SPACING,
+        );
+
+        assert!(recognize_letters(&input).is_err());
+    }
+
+    #[test]
+    fn is_letter_picture_rejects_plain_answers() {
+        assert!(!is_letter_picture("12345"));
+        assert!(is_letter_picture(SMALL_FONT[0].1));
+    }
+}
@@ -0,0 +1,152 @@
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TraceLevel {
+    Off,
+    Steps,
+    Detail,
+}
+
+impl FromStr for TraceLevel {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" | "off" => Ok(TraceLevel::Off),
+            "1" | "steps" => Ok(TraceLevel::Steps),
+            "2" | "detail" => Ok(TraceLevel::Detail),
+            _ => Err("Trace level must be one of 0 (off), 1 (steps) or 2 (detail)"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TraceEvent {
+    pub level: TraceLevel,
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        for (key, value) in &self.fields {
+            write!(f, " {key}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Sink that solvers record intermediate states to while solving a part.
+///
+/// Events above the configured level are dropped without building their fields, so
+/// solvers can record unconditionally and a disabled trace costs next to nothing.
+/// Events are also logged through `tracing`, steps at debug and details at trace level.
+pub struct Trace {
+    // Only read when recording, which no solver does if every year is compiled out.
+    #[cfg_attr(not(any(feature = "y2022", feature = "y2023")), allow(dead_code))]
+    level: TraceLevel,
+    events: Vec<TraceEvent>,
+}
+
+impl Trace {
+    pub fn new(level: TraceLevel) -> Trace {
+        Trace {
+            level,
+            events: vec![],
+        }
+    }
+
+    #[cfg(any(feature = "y2022", feature = "y2023"))]
+    pub fn is_enabled(&self, level: TraceLevel) -> bool {
+        level != TraceLevel::Off && level <= self.level
+    }
+
+    #[cfg(any(feature = "y2022", feature = "y2023"))]
+    pub fn record(
+        &mut self,
+        level: TraceLevel,
+        name: &'static str,
+        fields: impl FnOnce() -> Vec<(&'static str, String)>,
+    ) {
+        let logged = match level {
+            TraceLevel::Off => false,
+            TraceLevel::Steps => tracing::enabled!(tracing::Level::DEBUG),
+            TraceLevel::Detail => tracing::enabled!(tracing::Level::TRACE),
+        };
+        if !logged && !self.is_enabled(level) {
+            return;
+        }
+        let event = TraceEvent {
+            level,
+            name,
+            fields: fields(),
+        };
+        if logged {
+            match level {
+                TraceLevel::Detail => tracing::trace!("{event}"),
+                _ => tracing::debug!("{event}"),
+            }
+        }
+        if self.is_enabled(level) {
+            self.events.push(event);
+        }
+    }
+
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(any(feature = "y2022", feature = "y2023"))]
+    fn trace_keeps_events_up_to_configured_level() {
+        let expected = vec![TraceEvent {
+            level: TraceLevel::Steps,
+            name: "step",
+            fields: vec![("value", String::from("1"))],
+        }];
+
+        let mut trace = Trace::new(TraceLevel::Steps);
+        trace.record(TraceLevel::Steps, "step", || {
+            vec![("value", String::from("1"))]
+        });
+        trace.record(TraceLevel::Detail, "detail", || {
+            vec![("value", String::from("2"))]
+        });
+
+        assert_eq!(expected, trace.events());
+    }
+
+    #[test]
+    #[cfg(any(feature = "y2022", feature = "y2023"))]
+    fn disabled_trace_records_nothing() {
+        let mut trace = Trace::new(TraceLevel::Off);
+        trace.record(TraceLevel::Steps, "step", || {
+            panic!("fields should not be built")
+        });
+
+        assert!(trace.events().is_empty());
+    }
+
+    #[test]
+    fn trace_event_display() {
+        let expected = "crane_move count=3 from=1 to=3";
+
+        let event = TraceEvent {
+            level: TraceLevel::Steps,
+            name: "crane_move",
+            fields: vec![
+                ("count", String::from("3")),
+                ("from", String::from("1")),
+                ("to", String::from("3")),
+            ],
+        };
+
+        assert_eq!(expected, event.to_string());
+    }
+}
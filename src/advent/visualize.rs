@@ -0,0 +1,60 @@
+/// A single picture of a simulation, drawn as plain terminal lines.
+#[derive(Debug, PartialEq)]
+pub struct Frame {
+    pub caption: String,
+    pub lines: Vec<String>,
+}
+
+impl Frame {
+    #[cfg(any(feature = "y2022", feature = "y2023"))]
+    pub fn new(caption: String, lines: Vec<String>) -> Frame {
+        Frame { caption, lines }
+    }
+}
+
+#[cfg(feature = "y2023")]
+const HIGHLIGHT_START: &str = "\x1b[1;33m";
+#[cfg(feature = "y2023")]
+const HIGHLIGHT_END: &str = "\x1b[0m";
+
+/// Wraps the characters of `line` in `ranges` with an ANSI highlight.
+#[cfg(feature = "y2023")]
+pub fn highlight_ranges(line: &str, ranges: &[(usize, usize)]) -> String {
+    let mut highlighted = String::new();
+    for (index, character) in line.chars().enumerate() {
+        let in_range = ranges
+            .iter()
+            .any(|(start, end)| index >= *start && index <= *end);
+        if in_range && ranges.iter().any(|(start, _)| *start == index) {
+            highlighted.push_str(HIGHLIGHT_START);
+        }
+        highlighted.push(character);
+        if in_range && ranges.iter().any(|(_, end)| *end == index) {
+            highlighted.push_str(HIGHLIGHT_END);
+        }
+    }
+    highlighted
+}
+
+#[cfg(all(test, feature = "y2023"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlight_ranges_wraps_each_range() {
+        let expected = "\x1b[1;33m467\x1b[0m..\x1b[1;33m*\x1b[0m";
+
+        let actual = highlight_ranges("467..*", &[(0, 2), (5, 5)]);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn highlight_ranges_without_ranges_is_unchanged() {
+        let expected = "...*...";
+
+        let actual = highlight_ranges("...*...", &[]);
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -1,21 +1,31 @@
-use std::{error::Error, fs};
+use std::error::Error;
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{AdventDay, Parse, Trace};
 
 pub struct NewDay1Puzzle {}
 
+/// How many of the elves carrying the most calories part 2 adds up.
+const DEFAULT_TOP_ELVES: usize = 3;
+
+#[derive(Debug, Serialize)]
 pub struct Day1Puzzle {
     parsed_input: Vec<u32>,
+    #[serde(skip)]
+    top_elves: usize,
 }
 
 impl Parse for NewDay1Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path)?;
-
-        let individual_elf_calories = parse_elf_calories(&puzzle_input)?;
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
+        let individual_elf_calories = parse_elf_calories(puzzle_input)?;
 
         Ok(Box::new(Day1Puzzle {
             parsed_input: individual_elf_calories,
+            top_elves: DEFAULT_TOP_ELVES,
         }))
     }
 }
@@ -53,15 +63,33 @@ fn until_err<T, E>(err: &mut &mut Result<(), E>, item: Result<T, E>) -> Option<T
 }
 
 impl AdventDay for Day1Puzzle {
-    fn solve_first_puzzle(&self) -> String {
+    fn solve_first_puzzle(&self, _trace: &mut Trace) -> String {
         self.parsed_input.iter().max().unwrap().to_string()
     }
 
-    fn solve_second_puzzle(&self) -> String {
+    fn solve_second_puzzle(&self, _trace: &mut Trace) -> Option<String> {
         let mut copied_calories = self.parsed_input.clone();
         copied_calories.sort();
-        let total_calories: u32 = copied_calories[self.parsed_input.len() - 3..].iter().sum();
-        total_calories.to_string()
+        let top_elves = self.top_elves.min(copied_calories.len());
+        let total_calories: u32 = copied_calories[copied_calories.len() - top_elves..]
+            .iter()
+            .sum();
+        Some(total_calories.to_string())
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, u64)> {
+        vec![("top", self.top_elves as u64)]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: u64) -> Result<(), String> {
+        match name {
+            "top" => {
+                self.top_elves =
+                    usize::try_from(value).map_err(|_| format!("{value} elves is too many"))?
+            }
+            _ => return Err(format!("This day has no parameter named {name}")),
+        }
+        Ok(())
     }
 }
 
@@ -85,7 +113,7 @@ mod tests {
 10
 2
 8";
-        let actual = parse_elf_calories(&input)?;
+        let actual = parse_elf_calories(input)?;
 
         assert_eq!(expected, actual);
         Ok(())
@@ -105,7 +133,7 @@ is
 6
 not
 valid";
-        let actual = parse_elf_calories(&input);
+        let actual = parse_elf_calories(input);
 
         assert!(actual.is_err());
     }
@@ -125,7 +153,7 @@ is
 6
 9
 30";
-        let actual = parse_elf_calories(&input);
+        let actual = parse_elf_calories(input);
 
         assert!(actual.is_err());
     }
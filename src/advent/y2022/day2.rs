@@ -1,35 +1,37 @@
-use std::{error::Error, fs};
+use std::error::Error;
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{AdventDay, Parse, Trace};
 
 pub struct NewDay2Puzzle {}
 
-pub struct Day2Puzzle {
-    parsed_input: Vec<(String, String)>,
+#[derive(Debug, Serialize)]
+pub struct Day2Puzzle<'a> {
+    parsed_input: Vec<(&'a str, &'a str)>,
 }
 
 impl Parse for NewDay2Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path)?;
-
-        let parsed_input = parse_rock_paper_scissors_games(&puzzle_input)?;
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
+        let parsed_input = parse_rock_paper_scissors_games(puzzle_input)?;
 
-        Ok(Box::new(Day2Puzzle {
-            parsed_input: parsed_input,
-        }))
+        Ok(Box::new(Day2Puzzle { parsed_input }))
     }
 }
 
 fn parse_rock_paper_scissors_games(
     unparsed_games: &str,
-) -> Result<Vec<(String, String)>, Box<dyn Error>> {
+) -> Result<Vec<(&str, &str)>, Box<dyn Error>> {
     unparsed_games
         .split("\n")
         .map(extract_letters_and_validate)
-        .collect::<Result<Vec<(String, String)>, _>>()
+        .collect::<Result<Vec<(&str, &str)>, _>>()
 }
 
-fn extract_letters_and_validate(any_string: &str) -> Result<(String, String), Box<dyn Error>> {
+fn extract_letters_and_validate(any_string: &str) -> Result<(&str, &str), Box<dyn Error>> {
     let extracted_letters = extract_two_letters_separated_by_space(any_string)?;
     check_letters_are_valid(&extracted_letters)?;
     Ok(extracted_letters)
@@ -37,32 +39,28 @@ fn extract_letters_and_validate(any_string: &str) -> Result<(String, String), Bo
 
 fn extract_two_letters_separated_by_space(
     possibly_two_letters_separated_by_space: &str,
-) -> Result<(String, String), Box<dyn Error>> {
+) -> Result<(&str, &str), Box<dyn Error>> {
     let mut moves_played_iter = possibly_two_letters_separated_by_space.split(" ");
-    let opponent_played = String::from(
-        moves_played_iter
-            .next()
-            .ok_or("Invalid input: each line should contain two letters separated by a space")?,
-    );
-    let you_played = String::from(
-        moves_played_iter
-            .next()
-            .ok_or("Invalid input: each line should contain two letters separated by a space")?,
-    );
+    let opponent_played = moves_played_iter
+        .next()
+        .ok_or("Invalid input: each line should contain two letters separated by a space")?;
+    let you_played = moves_played_iter
+        .next()
+        .ok_or("Invalid input: each line should contain two letters separated by a space")?;
     Ok((opponent_played, you_played))
 }
 
-fn check_letters_are_valid(input: &(String, String)) -> Result<(), Box<dyn Error>> {
+fn check_letters_are_valid(input: &(&str, &str)) -> Result<(), Box<dyn Error>> {
     let allowed_characters_first_index = ["A", "B", "C"];
     let allowed_characters_second_index = ["X", "Y", "Z"];
 
-    if !allowed_characters_first_index.contains(&input.0.as_str()) {
+    if !allowed_characters_first_index.contains(&input.0) {
         return Err(
             "Invalid input: contained a character other than A, B, or C in the first column!"
                 .into(),
         );
     }
-    if !allowed_characters_second_index.contains(&input.1.as_str()) {
+    if !allowed_characters_second_index.contains(&input.1) {
         return Err(
             "Invalid input: contained a character other than X, Y, or Z in the second column!"
                 .into(),
@@ -71,13 +69,13 @@ fn check_letters_are_valid(input: &(String, String)) -> Result<(), Box<dyn Error
     Ok(())
 }
 
-impl AdventDay for Day2Puzzle {
-    fn solve_first_puzzle(&self) -> String {
+impl AdventDay for Day2Puzzle<'_> {
+    fn solve_first_puzzle(&self, _trace: &mut Trace) -> String {
         calculate_total_tournament_score_part_one(self.parsed_input.iter()).to_string()
     }
 
-    fn solve_second_puzzle(&self) -> String {
-        calculate_total_tournament_score_part_two(self.parsed_input.iter()).to_string()
+    fn solve_second_puzzle(&self, _trace: &mut Trace) -> Option<String> {
+        Some(calculate_total_tournament_score_part_two(self.parsed_input.iter()).to_string())
     }
 }
 
@@ -101,10 +99,10 @@ impl From<&str> for RockPaperScissors {
 
 impl RockPaperScissors {
     fn value(&self) -> u32 {
-        match self {
-            &RockPaperScissors::Rock => 1,
-            &RockPaperScissors::Paper => 2,
-            &RockPaperScissors::Scissors => 3,
+        match *self {
+            RockPaperScissors::Rock => 1,
+            RockPaperScissors::Paper => 2,
+            RockPaperScissors::Scissors => 3,
         }
     }
 }
@@ -129,10 +127,10 @@ impl From<&str> for WinLossDraw {
 
 impl WinLossDraw {
     fn value(&self) -> u32 {
-        match self {
-            &WinLossDraw::Loss => 0,
-            &WinLossDraw::Draw => 3,
-            &WinLossDraw::Win => 6,
+        match *self {
+            WinLossDraw::Loss => 0,
+            WinLossDraw::Draw => 3,
+            WinLossDraw::Win => 6,
         }
     }
 }
@@ -183,13 +181,12 @@ fn calculate_score(game_result: &WinLossDraw, you_played: &RockPaperScissors) ->
 }
 
 fn calculate_total_tournament_score_part_one<'a>(
-    games: impl Iterator<Item = &'a (String, String)>,
+    games: impl Iterator<Item = &'a (&'a str, &'a str)>,
 ) -> u32 {
     games
         .map(|x| {
-            let you_played = RockPaperScissors::from(x.1.as_str());
-            let game_result =
-                rock_paper_scissors(&RockPaperScissors::from(x.0.as_str()), &you_played);
+            let you_played = RockPaperScissors::from(x.1);
+            let game_result = rock_paper_scissors(&RockPaperScissors::from(x.0), &you_played);
             calculate_score(&game_result, &you_played)
         })
         .reduce(|acc, e| acc + e)
@@ -197,13 +194,12 @@ fn calculate_total_tournament_score_part_one<'a>(
 }
 
 fn calculate_total_tournament_score_part_two<'a>(
-    games: impl Iterator<Item = &'a (String, String)>,
+    games: impl Iterator<Item = &'a (&'a str, &'a str)>,
 ) -> u32 {
     games
         .map(|x| {
-            let game_result = WinLossDraw::from(x.1.as_str());
-            let you_played =
-                find_move_to_play(&game_result, &RockPaperScissors::from(x.0.as_str()));
+            let game_result = WinLossDraw::from(x.1);
+            let you_played = find_move_to_play(&game_result, &RockPaperScissors::from(x.0));
             calculate_score(&game_result, &you_played)
         })
         .reduce(|acc, e| acc + e)
@@ -435,11 +431,7 @@ mod tests {
     fn total_score_of_multiple_games_part_one() {
         let expected = 15;
 
-        let games = vec![
-            (String::from("A"), String::from("Y")),
-            (String::from("B"), String::from("X")),
-            (String::from("C"), String::from("Z")),
-        ];
+        let games = [("A", "Y"), ("B", "X"), ("C", "Z")];
         let actual = calculate_total_tournament_score_part_one(games.iter());
 
         assert_eq!(actual, expected)
@@ -449,11 +441,7 @@ mod tests {
     fn total_score_of_multiple_games_part_two() {
         let expected = 12;
 
-        let games = vec![
-            (String::from("A"), String::from("Y")),
-            (String::from("B"), String::from("X")),
-            (String::from("C"), String::from("Z")),
-        ];
+        let games = [("A", "Y"), ("B", "X"), ("C", "Z")];
         let actual = calculate_total_tournament_score_part_two(games.iter());
 
         assert_eq!(actual, expected)
@@ -461,7 +449,7 @@ mod tests {
 
     #[test]
     fn day_2_extract_two_letters() -> Result<(), Box<dyn Error>> {
-        let expected = (String::from("B"), String::from("X"));
+        let expected = ("B", "X");
 
         let input = "B X";
         let actual = extract_two_letters_separated_by_space(input)?;
@@ -488,7 +476,7 @@ mod tests {
 
     #[test]
     fn day_2_valid_letters() -> Result<(), Box<dyn Error>> {
-        let expected = (String::from("A"), String::from("Y"));
+        let expected = ("A", "Y");
 
         let input = expected;
         check_letters_are_valid(&input)?;
@@ -498,7 +486,7 @@ mod tests {
 
     #[test]
     fn day_2_invalid_first_letter() {
-        let input = (String::from("G"), String::from("X"));
+        let input = ("G", "X");
         let actual = check_letters_are_valid(&input);
 
         assert!(actual.is_err())
@@ -506,7 +494,7 @@ mod tests {
 
     #[test]
     fn day_2_invalid_second_letter() {
-        let input = (String::from("A"), String::from("M"));
+        let input = ("A", "M");
         let actual = check_letters_are_valid(&input);
 
         assert!(actual.is_err())
@@ -514,7 +502,7 @@ mod tests {
 
     #[test]
     fn day_2_invalid_invalid_letters() {
-        let input = (String::from("L"), String::from("E"));
+        let input = ("L", "E");
         let actual = check_letters_are_valid(&input);
 
         assert!(actual.is_err())
@@ -522,7 +510,7 @@ mod tests {
 
     #[test]
     fn day_2_invalid_empty_letters() {
-        let input = (String::from(" "), String::from(" "));
+        let input = (" ", " ");
         let actual = check_letters_are_valid(&input);
 
         assert!(actual.is_err())
@@ -530,10 +518,10 @@ mod tests {
 
     #[test]
     fn day_2_extract_and_validate_valid_letters() -> Result<(), Box<dyn Error>> {
-        let expected = (String::from("A"), String::from("Z"));
+        let expected = ("A", "Z");
 
         let input = "A Z";
-        let actual = extract_letters_and_validate(&input)?;
+        let actual = extract_letters_and_validate(input)?;
 
         assert_eq!(actual, expected);
         Ok(())
@@ -542,7 +530,7 @@ mod tests {
     #[test]
     fn day_2_extract_and_validate_invalid_first_letter() {
         let input = "Q Z";
-        let actual = extract_letters_and_validate(&input);
+        let actual = extract_letters_and_validate(input);
 
         assert!(actual.is_err());
     }
@@ -550,7 +538,7 @@ mod tests {
     #[test]
     fn day_2_extract_and_validate_invalid_second_letter() {
         let input = "B R";
-        let actual = extract_letters_and_validate(&input);
+        let actual = extract_letters_and_validate(input);
 
         assert!(actual.is_err());
     }
@@ -558,7 +546,7 @@ mod tests {
     #[test]
     fn day_2_extract_and_validate_empty_first_letter() {
         let input = "  Y";
-        let actual = extract_letters_and_validate(&input);
+        let actual = extract_letters_and_validate(input);
 
         assert!(actual.is_err());
     }
@@ -566,7 +554,7 @@ mod tests {
     #[test]
     fn day_2_extract_and_validate_empty_second_letter() {
         let input = "C  ";
-        let actual = extract_letters_and_validate(&input);
+        let actual = extract_letters_and_validate(input);
 
         assert!(actual.is_err());
     }
@@ -574,24 +562,20 @@ mod tests {
     #[test]
     fn day_2_extract_and_validate_empty() {
         let input = "";
-        let actual = extract_letters_and_validate(&input);
+        let actual = extract_letters_and_validate(input);
 
         assert!(actual.is_err());
     }
 
     #[test]
     fn parse_input() -> Result<(), Box<dyn Error>> {
-        let expected = vec![
-            (String::from("A"), String::from("Y")),
-            (String::from("B"), String::from("X")),
-            (String::from("C"), String::from("Z")),
-        ];
+        let expected = vec![("A", "Y"), ("B", "X"), ("C", "Z")];
 
         let input = "\
 A Y
 B X
 C Z";
-        let actual = parse_rock_paper_scissors_games(&input)?;
+        let actual = parse_rock_paper_scissors_games(input)?;
 
         assert_eq!(actual, expected);
         Ok(())
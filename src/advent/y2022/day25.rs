@@ -0,0 +1,125 @@
+use std::error::Error;
+
+use serde::Serialize;
+
+use super::super::{AdventDay, Parse, Trace, TraceLevel};
+
+pub struct NewDay25Puzzle {}
+
+#[derive(Debug, Serialize)]
+pub struct Day25Puzzle {
+    parsed_input: Vec<i64>,
+}
+
+impl Parse for NewDay25Puzzle {
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
+        let parsed_input = puzzle_input
+            .split("\n")
+            .map(snafu_to_decimal)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Box::new(Day25Puzzle { parsed_input }))
+    }
+}
+
+fn snafu_to_decimal(snafu: &str) -> Result<i64, Box<dyn Error>> {
+    snafu.bytes().try_fold(0, |decimal, digit| {
+        let digit_value = match digit {
+            b'2' => 2,
+            b'1' => 1,
+            b'0' => 0,
+            b'-' => -1,
+            b'=' => -2,
+            _ => return Err("Invalid input: SNAFU numbers only contain 2, 1, 0, - and =".into()),
+        };
+        Ok(decimal * 5 + digit_value)
+    })
+}
+
+fn decimal_to_snafu(mut decimal: i64) -> String {
+    if decimal == 0 {
+        return String::from("0");
+    }
+    let mut snafu_digits = vec![];
+    while decimal != 0 {
+        let (digit, carry) = match decimal.rem_euclid(5) {
+            3 => ('=', 1),
+            4 => ('-', 1),
+            remainder => (char::from(b'0' + remainder as u8), 0),
+        };
+        snafu_digits.push(digit);
+        decimal = decimal.div_euclid(5) + carry;
+    }
+    snafu_digits.iter().rev().collect()
+}
+
+impl AdventDay for Day25Puzzle {
+    fn solve_first_puzzle(&self, trace: &mut Trace) -> String {
+        let fuel_requirement = self.parsed_input.iter().sum::<i64>();
+        trace.record(TraceLevel::Steps, "fuel_requirement", || {
+            vec![("decimal", fuel_requirement.to_string())]
+        });
+        decimal_to_snafu(fuel_requirement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snafu_to_decimal_1() -> Result<(), Box<dyn Error>> {
+        let expected = 1747;
+
+        let actual = snafu_to_decimal("1=-0-2")?;
+
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+
+    #[test]
+    fn snafu_to_decimal_invalid_digit() {
+        assert!(snafu_to_decimal("1=3").is_err());
+    }
+
+    #[test]
+    fn decimal_to_snafu_1() {
+        let expected = "1121-1110-1=0";
+
+        let actual = decimal_to_snafu(314159265);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn solve_first_puzzle_1() -> Result<(), Box<dyn Error>> {
+        let expected = "2=-1=0";
+
+        let input = "\
+1=-0-2
+12111
+2=0=
+21
+2=01
+111
+20012
+112
+1=-1=
+1-12
+12
+1=
+122";
+        let advent_day = NewDay25Puzzle {}.parse_input(input)?;
+        let actual = advent_day.solve_first_puzzle(&mut Trace::new(TraceLevel::Off));
+
+        assert_eq!(expected, actual);
+        assert_eq!(
+            None,
+            advent_day.solve_second_puzzle(&mut Trace::new(TraceLevel::Off))
+        );
+        Ok(())
+    }
+}
@@ -1,18 +1,22 @@
-use std::{collections::HashSet, error::Error, fs};
+use std::{collections::HashSet, error::Error};
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{AdventDay, Parse, Trace};
 
 pub struct NewDay3Puzzle {}
 
+#[derive(Debug, Serialize)]
 pub struct Day3Puzzle {
     parsed_input: Vec<String>,
 }
 
 impl Parse for NewDay3Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path).unwrap();
-
-        let rucksack_compartments = parse_rucksacks(&puzzle_input)?;
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
+        let rucksack_compartments = parse_rucksacks(puzzle_input)?;
 
         Ok(Box::new(Day3Puzzle {
             parsed_input: rucksack_compartments,
@@ -38,12 +42,12 @@ fn parse_rucksacks(input: &str) -> Result<Vec<String>, Box<dyn Error>> {
 }
 
 impl AdventDay for Day3Puzzle {
-    fn solve_first_puzzle(&self) -> String {
+    fn solve_first_puzzle(&self, _trace: &mut Trace) -> String {
         calculate_sum_of_priorities_for_items_to_reorganize(&self.parsed_input).to_string()
     }
 
-    fn solve_second_puzzle(&self) -> String {
-        calculate_sum_of_priorities_of_badges(&self.parsed_input).to_string()
+    fn solve_second_puzzle(&self, _trace: &mut Trace) -> Option<String> {
+        Some(calculate_sum_of_priorities_of_badges(&self.parsed_input).to_string())
     }
 }
 
@@ -54,7 +58,7 @@ fn get_char_value(character: char) -> u32 {
     character as u32 - 96
 }
 
-fn split_string_in_half<'a>(string_to_split: &'a str) -> (&'a str, &'a str) {
+fn split_string_in_half(string_to_split: &str) -> (&str, &str) {
     let halfway_point_of_sting = string_to_split.len() / 2;
     let first_half = &string_to_split[..halfway_point_of_sting];
     let second_half = &string_to_split[halfway_point_of_sting..];
@@ -72,14 +76,14 @@ fn find_intersection_between_strings(strings: &[&str]) -> char {
     *running_intersection.iter().next().unwrap()
 }
 
-fn calculate_sum_of_priorities_for_items_to_reorganize(rucksacks: &Vec<String>) -> u32 {
+fn calculate_sum_of_priorities_for_items_to_reorganize(rucksacks: &[String]) -> u32 {
     rucksacks
         .iter()
         .map(|e| {
             let rucksack_compartments = split_string_in_half(e);
             let item_to_reorganize = find_intersection_between_strings(&[
-                &rucksack_compartments.0,
-                &rucksack_compartments.1,
+                rucksack_compartments.0,
+                rucksack_compartments.1,
             ]);
             get_char_value(item_to_reorganize)
         })
@@ -87,7 +91,7 @@ fn calculate_sum_of_priorities_for_items_to_reorganize(rucksacks: &Vec<String>)
         .unwrap()
 }
 
-fn calculate_sum_of_priorities_of_badges(rucksacks: &Vec<String>) -> u32 {
+fn calculate_sum_of_priorities_of_badges(rucksacks: &[String]) -> u32 {
     let mut accumulator = 0;
     for (index, rucksack) in rucksacks.iter().enumerate().filter(|e| e.0 % 3 == 0) {
         let badge = find_intersection_between_strings(&[
@@ -1,18 +1,22 @@
-use std::{error::Error, fs};
+use std::error::Error;
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{AdventDay, Parse, Trace};
 
 pub struct NewDay4Puzzle {}
 
+#[derive(Debug, Serialize)]
 pub struct Day4Puzzle {
     parsed_input: Vec<(CleaningAssignment, CleaningAssignment)>,
 }
 
 impl Parse for NewDay4Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path).unwrap();
-
-        let parsed_input = parse_cleaning_assignments(&puzzle_input)?;
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
+        let parsed_input = parse_cleaning_assignments(puzzle_input)?;
         Ok(Box::new(Day4Puzzle { parsed_input }))
     }
 }
@@ -53,16 +57,16 @@ fn parse_cleaning_assignments(
 }
 
 impl AdventDay for Day4Puzzle {
-    fn solve_first_puzzle(&self) -> String {
+    fn solve_first_puzzle(&self, _trace: &mut Trace) -> String {
         count_overlapping_cleaning_assignments(&self.parsed_input, true).to_string()
     }
 
-    fn solve_second_puzzle(&self) -> String {
-        count_overlapping_cleaning_assignments(&self.parsed_input, false).to_string()
+    fn solve_second_puzzle(&self, _trace: &mut Trace) -> Option<String> {
+        Some(count_overlapping_cleaning_assignments(&self.parsed_input, false).to_string())
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 struct CleaningAssignment {
     start_zone: u32,
     end_zone: u32,
@@ -77,19 +81,19 @@ impl CleaningAssignment {
     }
 
     fn contains_or_contained_in(&self, other: &CleaningAssignment) -> bool {
-        (&self.start_zone <= &other.start_zone && &self.end_zone >= &other.end_zone)
-            || (&self.start_zone >= &other.start_zone && &self.end_zone <= &other.end_zone)
+        (self.start_zone <= other.start_zone && self.end_zone >= other.end_zone)
+            || (self.start_zone >= other.start_zone && self.end_zone <= other.end_zone)
     }
 
     fn partial_overlap(&self, other: &CleaningAssignment) -> bool {
-        (&self.start_zone >= &other.start_zone && &self.start_zone <= &other.end_zone)
-            || (&self.end_zone >= &other.start_zone && &self.end_zone <= &other.end_zone)
-            || (self.contains_or_contained_in(&other))
+        (self.start_zone >= other.start_zone && self.start_zone <= other.end_zone)
+            || (self.end_zone >= other.start_zone && self.end_zone <= other.end_zone)
+            || (self.contains_or_contained_in(other))
     }
 }
 
 fn count_overlapping_cleaning_assignments(
-    cleaning_assingments_to_compare: &Vec<(CleaningAssignment, CleaningAssignment)>,
+    cleaning_assingments_to_compare: &[(CleaningAssignment, CleaningAssignment)],
     complete_overlap: bool,
 ) -> u32 {
     cleaning_assingments_to_compare
@@ -1,6 +1,8 @@
-use std::{collections::VecDeque, error::Error, fs};
+use std::{collections::VecDeque, error::Error};
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{AdventDay, Frame, Parse, Trace, TraceLevel};
 
 pub struct NewDay5Puzzle {}
 
@@ -11,16 +13,19 @@ const MOVE_FROM_EMPTY_STACK_ERROR_MESSAGE: &str = "Tried to move too many crates
 const STACK_EMPTY_AFTER_MOVES_ERROR_MESSAGE: &str =
     "Tried to find the crate at the top of the stack but the stack was empty";
 
+#[derive(Debug, Serialize)]
 pub struct Day5Puzzle {
-    stacks: Vec<VecDeque<String>>,
+    stacks: Vec<VecDeque<char>>,
     rearrangement_procedure: Vec<(u32, usize, usize)>,
 }
 
 impl Parse for NewDay5Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path).unwrap();
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
         let (stacks_str, count_of_stacks_str, rearrangement_procedure_str) =
-            split_input_into_sections(&puzzle_input);
+            split_input_into_sections(puzzle_input);
         let count_of_stacks = parse_count_of_stacks(count_of_stacks_str);
         let stacks = parse_stacks_of_crates(stacks_str, count_of_stacks);
         let rearrangement_procedure = parse_rearrangement_procedure(rearrangement_procedure_str);
@@ -42,16 +47,16 @@ fn split_input_into_sections(input: &str) -> (&str, &str, &str) {
     (stacks, count, moves)
 }
 
-fn parse_stacks_of_crates(stacks: &str, number_of_stacks: usize) -> Vec<VecDeque<String>> {
+fn parse_stacks_of_crates(stacks: &str, number_of_stacks: usize) -> Vec<VecDeque<char>> {
     let mut stacks_of_crates = vec![VecDeque::new(); number_of_stacks];
     let mut current_stack: usize = 0;
-    stacks.chars().enumerate().for_each(|x| {
+    stacks.bytes().enumerate().for_each(|x| {
         if x.0 > 0 && (x.0 - 1) % 4 == 0 {
-            if x.1 != ' ' {
-                stacks_of_crates[current_stack].push_back(x.1.to_string());
+            if x.1 != b' ' {
+                stacks_of_crates[current_stack].push_back(char::from(x.1));
             }
             current_stack += 1
-        } else if x.1 == '\n' {
+        } else if x.1 == b'\n' {
             current_stack = 0;
         }
     });
@@ -64,10 +69,11 @@ fn parse_count_of_stacks(count_of_stacks: &str) -> usize {
 }
 
 fn parse_rearrangement_procedure_line(rearrangement_procedure_line: &str) -> (u32, usize, usize) {
-    let words_to_ignore = ["move", "from", "to"];
+    // Every other word of `move N from A to B` is a number.
     let mut parsed_procedure_iter = rearrangement_procedure_line
-        .split(" ")
-        .filter(|x| !words_to_ignore.contains(x))
+        .split(' ')
+        .skip(1)
+        .step_by(2)
         .map(|x| x.parse::<u32>().unwrap());
     let number_to_move = parsed_procedure_iter.next().unwrap();
     let from_stack = parsed_procedure_iter.next().unwrap() as usize;
@@ -78,49 +84,148 @@ fn parse_rearrangement_procedure_line(rearrangement_procedure_line: &str) -> (u3
 fn parse_rearrangement_procedure(rearrangement_procedure: &str) -> Vec<(u32, usize, usize)> {
     rearrangement_procedure
         .lines()
-        .map(|l| parse_rearrangement_procedure_line(l))
+        .map(parse_rearrangement_procedure_line)
         .collect::<Vec<_>>()
 }
 
 impl AdventDay for Day5Puzzle {
-    fn solve_first_puzzle(&self) -> String {
-        let mut cloned_stacks = self.stacks.clone();
-        for procedure in &self.rearrangement_procedure {
-            cloned_stacks = move_crates_between_stacks(
-                cloned_stacks,
-                procedure.0,
-                procedure.1,
-                procedure.2,
-                false,
-            )
-            .unwrap();
-        }
-        retrieve_crates_on_top_of_stacks(&cloned_stacks).unwrap()
+    fn solve_first_puzzle(&self, trace: &mut Trace) -> String {
+        let final_stacks = apply_rearrangement_procedure(
+            &self.stacks,
+            &self.rearrangement_procedure,
+            false,
+            trace,
+        );
+        retrieve_crates_on_top_of_stacks(&final_stacks).unwrap()
     }
 
-    fn solve_second_puzzle(&self) -> String {
-        let mut cloned_stacks = self.stacks.clone();
-        for procedure in &self.rearrangement_procedure {
-            cloned_stacks = move_crates_between_stacks(
-                cloned_stacks,
+    fn solve_second_puzzle(&self, trace: &mut Trace) -> Option<String> {
+        let final_stacks =
+            apply_rearrangement_procedure(&self.stacks, &self.rearrangement_procedure, true, trace);
+        Some(retrieve_crates_on_top_of_stacks(&final_stacks).unwrap())
+    }
+
+    fn visualize_first_puzzle(&self) -> Option<Vec<Frame>> {
+        Some(visualize_rearrangement_procedure(
+            &self.stacks,
+            &self.rearrangement_procedure,
+            false,
+        ))
+    }
+
+    fn visualize_second_puzzle(&self) -> Option<Vec<Frame>> {
+        Some(visualize_rearrangement_procedure(
+            &self.stacks,
+            &self.rearrangement_procedure,
+            true,
+        ))
+    }
+}
+
+fn apply_rearrangement_procedure(
+    stacks: &[VecDeque<char>],
+    rearrangement_procedure: &[(u32, usize, usize)],
+    move_all_at_once: bool,
+    trace: &mut Trace,
+) -> Vec<VecDeque<char>> {
+    let mut cloned_stacks = stacks.to_vec();
+    for procedure in rearrangement_procedure {
+        cloned_stacks = move_crates_between_stacks(
+            cloned_stacks,
+            procedure.0,
+            procedure.1,
+            procedure.2,
+            move_all_at_once,
+        )
+        .unwrap();
+        trace.record(TraceLevel::Steps, "crane_move", || {
+            vec![
+                ("count", procedure.0.to_string()),
+                ("from", procedure.1.to_string()),
+                ("to", procedure.2.to_string()),
+            ]
+        });
+        trace.record(TraceLevel::Detail, "stacks", || {
+            vec![("crates", format_stacks(&cloned_stacks))]
+        });
+    }
+    cloned_stacks
+}
+
+fn format_stacks(stacks: &[VecDeque<char>]) -> String {
+    stacks
+        .iter()
+        .map(|stack| stack.iter().rev().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+fn visualize_rearrangement_procedure(
+    stacks: &[VecDeque<char>],
+    rearrangement_procedure: &[(u32, usize, usize)],
+    move_all_at_once: bool,
+) -> Vec<Frame> {
+    let mut cloned_stacks = stacks.to_vec();
+    let mut frames = vec![Frame::new(
+        String::from("starting stacks"),
+        draw_stacks(&cloned_stacks),
+    )];
+    for (step, procedure) in rearrangement_procedure.iter().enumerate() {
+        cloned_stacks = move_crates_between_stacks(
+            cloned_stacks,
+            procedure.0,
+            procedure.1,
+            procedure.2,
+            move_all_at_once,
+        )
+        .unwrap();
+        frames.push(Frame::new(
+            format!(
+                "move {} from {} to {} ({}/{})",
                 procedure.0,
                 procedure.1,
                 procedure.2,
-                true,
-            )
-            .unwrap();
-        }
-        retrieve_crates_on_top_of_stacks(&cloned_stacks).unwrap()
+                step + 1,
+                rearrangement_procedure.len()
+            ),
+            draw_stacks(&cloned_stacks),
+        ));
+    }
+    frames
+}
+
+fn draw_stacks(stacks: &[VecDeque<char>]) -> Vec<String> {
+    let tallest_stack = stacks.iter().map(VecDeque::len).max().unwrap_or(0);
+    let mut lines = vec![];
+    for height in (0..tallest_stack).rev() {
+        let line = stacks
+            .iter()
+            .map(|stack| {
+                if height < stack.len() {
+                    format!("[{}]", stack[stack.len() - 1 - height])
+                } else {
+                    String::from("   ")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        lines.push(line);
     }
+    let stack_numbers = (1..=stacks.len())
+        .map(|stack_number| format!(" {stack_number} "))
+        .collect::<Vec<_>>()
+        .join(" ");
+    lines.push(stack_numbers);
+    lines
 }
 
 fn move_crates_between_stacks(
-    mut stacks: Vec<VecDeque<String>>,
+    mut stacks: Vec<VecDeque<char>>,
     number_to_move: u32,
     from_stack: usize,
     to_stack: usize,
     move_all_at_once: bool,
-) -> Result<Vec<VecDeque<String>>, &'static str> {
+) -> Result<Vec<VecDeque<char>>, &'static str> {
     let mut crates_to_move = VecDeque::new();
     for _ in 0..number_to_move {
         let crate_to_move = stacks
@@ -148,12 +253,10 @@ fn move_crates_between_stacks(
     Ok(stacks)
 }
 
-fn retrieve_crates_on_top_of_stacks(
-    stacks: &Vec<VecDeque<String>>,
-) -> Result<String, &'static str> {
+fn retrieve_crates_on_top_of_stacks(stacks: &Vec<VecDeque<char>>) -> Result<String, &'static str> {
     let mut crates_at_top_stacks = String::from("");
     for stack in stacks {
-        crates_at_top_stacks.push_str(stack.front().ok_or(STACK_EMPTY_AFTER_MOVES_ERROR_MESSAGE)?);
+        crates_at_top_stacks.push(*stack.front().ok_or(STACK_EMPTY_AFTER_MOVES_ERROR_MESSAGE)?);
     }
     Ok(crates_at_top_stacks)
 }
@@ -165,15 +268,15 @@ mod tests {
     #[test]
     fn day_5_move_1_from_2_to_1() -> Result<(), Box<dyn Error>> {
         let expected = vec![
-            VecDeque::from([String::from("D"), String::from("N"), String::from("Z")]),
-            VecDeque::from([String::from("C"), String::from("M")]),
-            VecDeque::from([String::from("P")]),
+            VecDeque::from(['D', 'N', 'Z']),
+            VecDeque::from(['C', 'M']),
+            VecDeque::from(['P']),
         ];
 
         let input = vec![
-            VecDeque::from([String::from("N"), String::from("Z")]),
-            VecDeque::from([String::from("D"), String::from("C"), String::from("M")]),
-            VecDeque::from([String::from("P")]),
+            VecDeque::from(['N', 'Z']),
+            VecDeque::from(['D', 'C', 'M']),
+            VecDeque::from(['P']),
         ];
         let actual = move_crates_between_stacks(input, 1, 2, 1, false)?;
 
@@ -185,19 +288,14 @@ mod tests {
     fn day_5_move_3_from_1_to_3() -> Result<(), Box<dyn Error>> {
         let expected = vec![
             VecDeque::from([]),
-            VecDeque::from([String::from("C"), String::from("M")]),
-            VecDeque::from([
-                String::from("Z"),
-                String::from("N"),
-                String::from("D"),
-                String::from("P"),
-            ]),
+            VecDeque::from(['C', 'M']),
+            VecDeque::from(['Z', 'N', 'D', 'P']),
         ];
 
         let input = vec![
-            VecDeque::from([String::from("D"), String::from("N"), String::from("Z")]),
-            VecDeque::from([String::from("C"), String::from("M")]),
-            VecDeque::from([String::from("P")]),
+            VecDeque::from(['D', 'N', 'Z']),
+            VecDeque::from(['C', 'M']),
+            VecDeque::from(['P']),
         ];
         let actual = move_crates_between_stacks(input, 3, 1, 3, false)?;
 
@@ -209,19 +307,14 @@ mod tests {
     fn day_5_move_3_from_1_to_3_crate_mover_9001() -> Result<(), Box<dyn Error>> {
         let expected = vec![
             VecDeque::from([]),
-            VecDeque::from([String::from("C"), String::from("M")]),
-            VecDeque::from([
-                String::from("D"),
-                String::from("N"),
-                String::from("Z"),
-                String::from("P"),
-            ]),
+            VecDeque::from(['C', 'M']),
+            VecDeque::from(['D', 'N', 'Z', 'P']),
         ];
 
         let input = vec![
-            VecDeque::from([String::from("D"), String::from("N"), String::from("Z")]),
-            VecDeque::from([String::from("C"), String::from("M")]),
-            VecDeque::from([String::from("P")]),
+            VecDeque::from(['D', 'N', 'Z']),
+            VecDeque::from(['C', 'M']),
+            VecDeque::from(['P']),
         ];
         let actual = move_crates_between_stacks(input, 3, 1, 3, true)?;
 
@@ -232,25 +325,15 @@ mod tests {
     #[test]
     fn day_5_move_2_from_2_to_1() -> Result<(), Box<dyn Error>> {
         let expected = vec![
-            VecDeque::from([String::from("M"), String::from("C")]),
+            VecDeque::from(['M', 'C']),
             VecDeque::from([]),
-            VecDeque::from([
-                String::from("Z"),
-                String::from("N"),
-                String::from("D"),
-                String::from("P"),
-            ]),
+            VecDeque::from(['Z', 'N', 'D', 'P']),
         ];
 
         let input = vec![
             VecDeque::from([]),
-            VecDeque::from([String::from("C"), String::from("M")]),
-            VecDeque::from([
-                String::from("Z"),
-                String::from("N"),
-                String::from("D"),
-                String::from("P"),
-            ]),
+            VecDeque::from(['C', 'M']),
+            VecDeque::from(['Z', 'N', 'D', 'P']),
         ];
         let actual = move_crates_between_stacks(input, 2, 2, 1, false)?;
 
@@ -261,25 +344,15 @@ mod tests {
     #[test]
     fn day_5_move_1_from_1_to_2() -> Result<(), Box<dyn Error>> {
         let expected = vec![
-            VecDeque::from([String::from("C")]),
-            VecDeque::from([String::from("M")]),
-            VecDeque::from([
-                String::from("Z"),
-                String::from("N"),
-                String::from("D"),
-                String::from("P"),
-            ]),
+            VecDeque::from(['C']),
+            VecDeque::from(['M']),
+            VecDeque::from(['Z', 'N', 'D', 'P']),
         ];
 
         let input = vec![
-            VecDeque::from([String::from("M"), String::from("C")]),
+            VecDeque::from(['M', 'C']),
             VecDeque::from([]),
-            VecDeque::from([
-                String::from("Z"),
-                String::from("N"),
-                String::from("D"),
-                String::from("P"),
-            ]),
+            VecDeque::from(['Z', 'N', 'D', 'P']),
         ];
         let actual = move_crates_between_stacks(input, 1, 1, 2, false)?;
 
@@ -292,9 +365,9 @@ mod tests {
         let expected = Some(OVERFLOW_ERROR_MESSAGE);
 
         let input = vec![
-            VecDeque::from([String::from("N"), String::from("Z")]),
-            VecDeque::from([String::from("D"), String::from("C"), String::from("M")]),
-            VecDeque::from([String::from("P")]),
+            VecDeque::from(['N', 'Z']),
+            VecDeque::from(['D', 'C', 'M']),
+            VecDeque::from(['P']),
         ];
         let actual = move_crates_between_stacks(input, 1, 0, 2, false);
 
@@ -306,9 +379,9 @@ mod tests {
         let expected = Some(OVERFLOW_ERROR_MESSAGE);
 
         let input = vec![
-            VecDeque::from([String::from("N"), String::from("Z")]),
-            VecDeque::from([String::from("D"), String::from("C"), String::from("M")]),
-            VecDeque::from([String::from("P")]),
+            VecDeque::from(['N', 'Z']),
+            VecDeque::from(['D', 'C', 'M']),
+            VecDeque::from(['P']),
         ];
         let actual = move_crates_between_stacks(input, 1, 1, 0, false);
 
@@ -320,9 +393,9 @@ mod tests {
         let expected = Some(STACK_INDEX_ERROR_MESSAGE);
 
         let input = vec![
-            VecDeque::from([String::from("N"), String::from("Z")]),
-            VecDeque::from([String::from("D"), String::from("C"), String::from("M")]),
-            VecDeque::from([String::from("P")]),
+            VecDeque::from(['N', 'Z']),
+            VecDeque::from(['D', 'C', 'M']),
+            VecDeque::from(['P']),
         ];
         let actual = move_crates_between_stacks(input, 1, 5, 2, false);
 
@@ -334,9 +407,9 @@ mod tests {
         let expected = Some(STACK_INDEX_ERROR_MESSAGE);
 
         let input = vec![
-            VecDeque::from([String::from("N"), String::from("Z")]),
-            VecDeque::from([String::from("D"), String::from("C"), String::from("M")]),
-            VecDeque::from([String::from("P")]),
+            VecDeque::from(['N', 'Z']),
+            VecDeque::from(['D', 'C', 'M']),
+            VecDeque::from(['P']),
         ];
         let actual = move_crates_between_stacks(input, 1, 1, 7, false);
 
@@ -348,9 +421,9 @@ mod tests {
         let expected = Some(MOVE_FROM_EMPTY_STACK_ERROR_MESSAGE);
 
         let input = vec![
-            VecDeque::from([String::from("N"), String::from("Z")]),
-            VecDeque::from([String::from("D"), String::from("C"), String::from("M")]),
-            VecDeque::from([String::from("P")]),
+            VecDeque::from(['N', 'Z']),
+            VecDeque::from(['D', 'C', 'M']),
+            VecDeque::from(['P']),
         ];
         let actual = move_crates_between_stacks(input, 4, 1, 3, false);
 
@@ -362,14 +435,9 @@ mod tests {
         let expected = "CMZ";
 
         let input = vec![
-            VecDeque::from([String::from("C")]),
-            VecDeque::from([String::from("M")]),
-            VecDeque::from([
-                String::from("Z"),
-                String::from("N"),
-                String::from("D"),
-                String::from("P"),
-            ]),
+            VecDeque::from(['C']),
+            VecDeque::from(['M']),
+            VecDeque::from(['Z', 'N', 'D', 'P']),
         ];
         let actual = retrieve_crates_on_top_of_stacks(&input)?;
 
@@ -377,12 +445,45 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn day_5_format_stacks_bottom_to_top() {
+        let expected = "ZND|MC|P";
+
+        let input = vec![
+            VecDeque::from(['D', 'N', 'Z']),
+            VecDeque::from(['C', 'M']),
+            VecDeque::from(['P']),
+        ];
+        let actual = format_stacks(&input);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn day_5_draw_stacks() {
+        let expected = vec![
+            String::from("[D]        "),
+            String::from("[N] [C]    "),
+            String::from("[Z] [M] [P]"),
+            String::from(" 1   2   3 "),
+        ];
+
+        let input = vec![
+            VecDeque::from(['D', 'N', 'Z']),
+            VecDeque::from(['C', 'M']),
+            VecDeque::from(['P']),
+        ];
+        let actual = draw_stacks(&input);
+
+        assert_eq!(expected, actual);
+    }
+
     #[test]
     fn day_5_parse_stacks_of_crates() {
         let expected = vec![
-            VecDeque::from([String::from("D"), String::from("N"), String::from("Z")]),
-            VecDeque::from([String::from("C"), String::from("M")]),
-            VecDeque::from([String::from("P")]),
+            VecDeque::from(['D', 'N', 'Z']),
+            VecDeque::from(['C', 'M']),
+            VecDeque::from(['P']),
         ];
 
         let input = "\
@@ -396,26 +497,11 @@ mod tests {
     #[test]
     fn day_5_parse_more_stacks_of_crates() {
         let expected = vec![
-            VecDeque::from([
-                String::from("R"),
-                String::from("D"),
-                String::from("N"),
-                String::from("Z"),
-            ]),
-            VecDeque::from([String::from("V"), String::from("C"), String::from("M")]),
-            VecDeque::from([
-                String::from("B"),
-                String::from("S"),
-                String::from("Q"),
-                String::from("P"),
-            ]),
-            VecDeque::from([String::from("G"), String::from("A")]),
-            VecDeque::from([
-                String::from("I"),
-                String::from("Y"),
-                String::from("O"),
-                String::from("U"),
-            ]),
+            VecDeque::from(['R', 'D', 'N', 'Z']),
+            VecDeque::from(['V', 'C', 'M']),
+            VecDeque::from(['B', 'S', 'Q', 'P']),
+            VecDeque::from(['G', 'A']),
+            VecDeque::from(['I', 'Y', 'O', 'U']),
         ];
 
         let input = "\
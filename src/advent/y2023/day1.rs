@@ -1,6 +1,8 @@
-use std::{error::Error, fs};
+use std::error::Error;
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{AdventDay, Parse, Trace};
 
 const SPELLED_OUT_NUMBERS_NUMERIC_EQUIVALENT: &[(&str, &str); 9] = &[
     ("one", "1"),
@@ -16,14 +18,16 @@ const SPELLED_OUT_NUMBERS_NUMERIC_EQUIVALENT: &[(&str, &str); 9] = &[
 
 pub struct NewDay1Puzzle {}
 
+#[derive(Debug, Serialize)]
 pub struct Day1Puzzle {
     parsed_input: Vec<String>,
 }
 
 impl Parse for NewDay1Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path)?;
-
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
         let parsed_input = puzzle_input
             .split("\n")
             .map(String::from)
@@ -34,18 +38,22 @@ impl Parse for NewDay1Puzzle {
 }
 
 impl AdventDay for Day1Puzzle {
-    fn solve_first_puzzle(&self) -> String {
+    fn solve_first_puzzle(&self, _trace: &mut Trace) -> String {
         extract_numbers_from_strings_and_find_sum(self.parsed_input.iter()).to_string()
     }
 
-    fn solve_second_puzzle(&self) -> String {
+    fn solve_second_puzzle(&self, _trace: &mut Trace) -> Option<String> {
         let input_with_spelled_out_numbers_replaced = self
             .parsed_input
             .iter()
             .map(|x| convert_spelled_out_numbers_to_numeric(x))
             .collect::<Vec<String>>();
-        extract_numbers_from_strings_and_find_sum(input_with_spelled_out_numbers_replaced.iter())
-            .to_string()
+        Some(
+            extract_numbers_from_strings_and_find_sum(
+                input_with_spelled_out_numbers_replaced.iter(),
+            )
+            .to_string(),
+        )
     }
 }
 
@@ -66,7 +74,7 @@ fn extract_first_and_last_number_into_int(string: &str) -> u32 {
 
 fn extract_numbers_from_strings_and_find_sum<'a>(strings: impl Iterator<Item = &'a String>) -> u32 {
     strings
-        .map(|x| extract_first_and_last_number_into_int(&x))
+        .map(|x| extract_first_and_last_number_into_int(x))
         .reduce(|acc, e| acc + e)
         .unwrap()
 }
@@ -224,7 +232,7 @@ mod tests {
     fn extract_numbers_and_find_sum_is_142() {
         let expected: u32 = 142;
 
-        let input = vec![
+        let input = [
             String::from("1abc2"),
             String::from("pqr3stu8vwx"),
             String::from("a1b2c3d4e5f"),
@@ -370,7 +378,7 @@ mod tests {
     fn extract_numbers_and_find_sum_is_281() {
         let expected: u32 = 281;
 
-        let input = vec![
+        let input = [
             String::from("21ninetwo19"),
             String::from("8wothreeeightwo3"),
             String::from("abc12threexyzabcone23xyz"),
@@ -1,23 +1,39 @@
-use std::{error::Error, fs};
+use std::error::Error;
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{AdventDay, Parse, Trace};
 
 pub struct NewDay2Puzzle {}
 
+/// The cubes in the bag that part 1 checks each game against.
+const DEFAULT_BAG: RevealedCubes = RevealedCubes {
+    red: 12,
+    blue: 14,
+    green: 13,
+};
+
+#[derive(Debug, Serialize)]
 pub struct Day2Puzzle {
     parsed_input: Vec<Game>,
+    #[serde(skip)]
+    bag: RevealedCubes,
 }
 
 impl Parse for NewDay2Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path)?;
-
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
         let parsed_input = puzzle_input
             .split("\n")
             .map(parse_game_string)
             .collect::<Vec<_>>();
 
-        Ok(Box::new(Day2Puzzle { parsed_input }))
+        Ok(Box::new(Day2Puzzle {
+            parsed_input,
+            bag: DEFAULT_BAG,
+        }))
     }
 }
 
@@ -60,7 +76,7 @@ fn parse_game_string(game_string: &str) -> Game {
     Game::new(game_identifier, revealed_cubes)
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 struct RevealedCubes {
     red: u32,
     blue: u32,
@@ -83,7 +99,7 @@ impl RevealedCubes {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 struct Game {
     identifier: u32,
     revealed_cubes: Vec<RevealedCubes>,
@@ -99,12 +115,13 @@ impl Game {
 }
 
 impl AdventDay for Day2Puzzle {
-    fn solve_first_puzzle(&self) -> String {
-        find_sum_of_identifiers_of_valid_games(&self.parsed_input).to_string()
+    fn solve_first_puzzle(&self, _trace: &mut Trace) -> String {
+        find_sum_of_identifiers_of_valid_games(&self.parsed_input, &self.bag).to_string()
     }
 
-    fn solve_second_puzzle(&self) -> String {
-        self.parsed_input
+    fn solve_second_puzzle(&self, _trace: &mut Trace) -> Option<String> {
+        let sum_of_powers = self
+            .parsed_input
             .iter()
             .map(|game| {
                 let largest_number_of_each_colored_cube =
@@ -114,37 +131,55 @@ impl AdventDay for Day2Puzzle {
                 calculate_power_of_cubes(&largest_number_of_each_colored_cube)
             })
             .reduce(|acc, e| acc + e)
-            .unwrap()
-            .to_string()
+            .unwrap();
+        Some(sum_of_powers.to_string())
+    }
+
+    fn parameters(&self) -> Vec<(&'static str, u64)> {
+        vec![
+            ("red", u64::from(self.bag.red)),
+            ("green", u64::from(self.bag.green)),
+            ("blue", u64::from(self.bag.blue)),
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: u64) -> Result<(), String> {
+        let count = u32::try_from(value).map_err(|_| format!("{value} cubes is too many"))?;
+        match name {
+            "red" => self.bag.red = count,
+            "green" => self.bag.green = count,
+            "blue" => self.bag.blue = count,
+            _ => return Err(format!("This day has no parameter named {name}")),
+        }
+        Ok(())
     }
 }
 
-fn check_if_cubes_invalidate_game(revealed_cubes: &RevealedCubes) -> bool {
-    if revealed_cubes.red > 12 {
+fn check_if_cubes_invalidate_game(revealed_cubes: &RevealedCubes, bag: &RevealedCubes) -> bool {
+    if revealed_cubes.red > bag.red {
         return true;
     }
-    if revealed_cubes.green > 13 {
+    if revealed_cubes.green > bag.green {
         return true;
     }
-    if revealed_cubes.blue > 14 {
+    if revealed_cubes.blue > bag.blue {
         return true;
     }
     false
 }
 
-fn check_if_game_is_valid(cubes_revealed_in_game: &Vec<RevealedCubes>) -> bool {
+fn check_if_game_is_valid(cubes_revealed_in_game: &[RevealedCubes], bag: &RevealedCubes) -> bool {
     !cubes_revealed_in_game
         .iter()
-        .any(check_if_cubes_invalidate_game)
+        .any(|revealed_cubes| check_if_cubes_invalidate_game(revealed_cubes, bag))
 }
 
-fn find_sum_of_identifiers_of_valid_games(games: &Vec<Game>) -> u32 {
+fn find_sum_of_identifiers_of_valid_games(games: &[Game], bag: &RevealedCubes) -> u32 {
     games
         .iter()
-        .filter(|x| check_if_game_is_valid(&x.revealed_cubes))
+        .filter(|x| check_if_game_is_valid(&x.revealed_cubes, bag))
         .map(|valid| valid.identifier)
-        .reduce(|acc, e| acc + e)
-        .unwrap()
+        .sum()
 }
 
 fn find_largest_number_of_each_colored_cube_in_revealed_cubes(
@@ -180,7 +215,7 @@ mod tests {
     fn check_if_cubes_invalidate_game_all_valid_1() {
         let expected = false;
 
-        let actual = check_if_cubes_invalidate_game(&RevealedCubes::new(4, 0, 3));
+        let actual = check_if_cubes_invalidate_game(&RevealedCubes::new(4, 0, 3), &DEFAULT_BAG);
 
         assert_eq!(expected, actual);
     }
@@ -189,7 +224,7 @@ mod tests {
     fn check_if_cubes_invalidate_game_all_valid_2() {
         let expected = false;
 
-        let actual = check_if_cubes_invalidate_game(&RevealedCubes::new(1, 2, 6));
+        let actual = check_if_cubes_invalidate_game(&RevealedCubes::new(1, 2, 6), &DEFAULT_BAG);
 
         assert_eq!(expected, actual);
     }
@@ -198,7 +233,7 @@ mod tests {
     fn check_if_cubes_invalidate_game_all_valid_3() {
         let expected = false;
 
-        let actual = check_if_cubes_invalidate_game(&RevealedCubes::new(0, 2, 0));
+        let actual = check_if_cubes_invalidate_game(&RevealedCubes::new(0, 2, 0), &DEFAULT_BAG);
 
         assert_eq!(expected, actual);
     }
@@ -207,7 +242,7 @@ mod tests {
     fn check_if_cubes_invalidate_game_red_invalid() {
         let expected = true;
 
-        let actual = check_if_cubes_invalidate_game(&RevealedCubes::new(20, 8, 6));
+        let actual = check_if_cubes_invalidate_game(&RevealedCubes::new(20, 8, 6), &DEFAULT_BAG);
 
         assert_eq!(expected, actual);
     }
@@ -216,7 +251,7 @@ mod tests {
     fn check_if_cubes_invalidate_game_blue_invalid() {
         let expected = true;
 
-        let actual = check_if_cubes_invalidate_game(&RevealedCubes::new(12, 3, 15));
+        let actual = check_if_cubes_invalidate_game(&RevealedCubes::new(12, 3, 15), &DEFAULT_BAG);
 
         assert_eq!(expected, actual);
     }
@@ -225,7 +260,7 @@ mod tests {
     fn check_if_cubes_invalidate_game_green_invalid() {
         let expected = true;
 
-        let actual = check_if_cubes_invalidate_game(&RevealedCubes::new(5, 17, 10));
+        let actual = check_if_cubes_invalidate_game(&RevealedCubes::new(5, 17, 10), &DEFAULT_BAG);
 
         assert_eq!(expected, actual);
     }
@@ -239,7 +274,7 @@ mod tests {
             RevealedCubes::new(1, 2, 6),
             RevealedCubes::new(0, 2, 0),
         ];
-        let actual = check_if_game_is_valid(&input);
+        let actual = check_if_game_is_valid(&input, &DEFAULT_BAG);
 
         assert_eq!(expected, actual);
     }
@@ -253,7 +288,7 @@ mod tests {
             RevealedCubes::new(1, 3, 4),
             RevealedCubes::new(0, 1, 1),
         ];
-        let actual = check_if_game_is_valid(&input);
+        let actual = check_if_game_is_valid(&input, &DEFAULT_BAG);
 
         assert_eq!(expected, actual);
     }
@@ -267,7 +302,7 @@ mod tests {
             RevealedCubes::new(4, 13, 5),
             RevealedCubes::new(1, 5, 0),
         ];
-        let actual = check_if_game_is_valid(&input);
+        let actual = check_if_game_is_valid(&input, &DEFAULT_BAG);
 
         assert_eq!(expected, actual);
     }
@@ -281,7 +316,7 @@ mod tests {
             RevealedCubes::new(6, 3, 0),
             RevealedCubes::new(12, 3, 15),
         ];
-        let actual = check_if_game_is_valid(&input);
+        let actual = check_if_game_is_valid(&input, &DEFAULT_BAG);
 
         assert_eq!(expected, actual);
     }
@@ -328,7 +363,7 @@ mod tests {
                 vec![RevealedCubes::new(6, 3, 1), RevealedCubes::new(1, 2, 2)],
             ),
         ];
-        let actual = find_sum_of_identifiers_of_valid_games(&input);
+        let actual = find_sum_of_identifiers_of_valid_games(&input, &DEFAULT_BAG);
 
         assert_eq!(expected, actual);
     }
@@ -1,28 +1,29 @@
-use std::{error::Error, fs};
+use std::error::Error;
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{visualize::highlight_ranges, AdventDay, Frame, Parse, Trace};
 
 pub struct NewDay3Puzzle {}
 
-pub struct Day3Puzzle {
-    parsed_input: Vec<Vec<String>>,
+#[derive(Debug, Serialize)]
+pub struct Day3Puzzle<'a> {
+    parsed_input: Vec<&'a str>,
 }
 
 impl Parse for NewDay3Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path)?;
-
-        let parsed_input = puzzle_input
-            .split("\n")
-            .map(|line| line.chars().map(String::from).collect::<Vec<String>>())
-            .collect::<Vec<_>>();
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
+        let parsed_input = puzzle_input.split("\n").collect::<Vec<_>>();
 
         Ok(Box::new(Day3Puzzle { parsed_input }))
     }
 }
 
-impl AdventDay for Day3Puzzle {
-    fn solve_first_puzzle(&self) -> String {
+impl AdventDay for Day3Puzzle<'_> {
+    fn solve_first_puzzle(&self, _trace: &mut Trace) -> String {
         extract_part_numbers_to_sum(&self.parsed_input, false)
             .into_iter()
             .reduce(|acc, e| acc + e)
@@ -30,7 +31,7 @@ impl AdventDay for Day3Puzzle {
             .to_string()
     }
 
-    fn solve_second_puzzle(&self) -> String {
+    fn solve_second_puzzle(&self, _trace: &mut Trace) -> Option<String> {
         let gear_part_numbers = extract_part_numbers_to_sum(&self.parsed_input, true);
         let mut running_total = 0;
 
@@ -39,62 +40,44 @@ impl AdventDay for Day3Puzzle {
                 running_total += part_number * gear_part_numbers[index + 1]
             }
         }
-        running_total.to_string()
+        Some(running_total.to_string())
+    }
+
+    fn visualize_first_puzzle(&self) -> Option<Vec<Frame>> {
+        Some(visualize_engine_schematic(&self.parsed_input, false))
     }
-}
 
-fn combine_split_part_number_descending(split_number: &Vec<String>, start_index: usize) -> String {
-    let mut digits_before_index = String::new();
-    if start_index == 0 {
-        return digits_before_index;
+    fn visualize_second_puzzle(&self) -> Option<Vec<Frame>> {
+        Some(visualize_engine_schematic(&self.parsed_input, true))
     }
+}
+
+fn combine_split_part_number_descending(split_number: &[u8], start_index: usize) -> &[u8] {
     let mut current_index = start_index;
-    while current_index > 0 {
+    while current_index > 0 && split_number[current_index - 1].is_ascii_digit() {
         current_index -= 1;
-        let current_value = &split_number[current_index];
-        if !current_value.parse::<u32>().is_ok() {
-            break;
-        }
-        digits_before_index.push_str(current_value);
     }
-
-    digits_before_index.chars().rev().collect::<String>()
+    &split_number[current_index..start_index]
 }
 
-fn combine_split_part_number_ascending(split_number: &Vec<String>, start_index: usize) -> String {
-    let mut digits_after_index = String::new();
-    if start_index == split_number.len() - 1 {
-        return digits_after_index;
-    }
+fn combine_split_part_number_ascending(split_number: &[u8], start_index: usize) -> &[u8] {
     let mut current_index = start_index;
-    while current_index < split_number.len() - 1 {
+    while current_index < split_number.len() - 1 && split_number[current_index + 1].is_ascii_digit()
+    {
         current_index += 1;
-        let current_value = &split_number[current_index];
-        if !current_value.parse::<u32>().is_ok() {
-            break;
-        }
-        digits_after_index.push_str(current_value);
     }
-
-    digits_after_index
+    &split_number[start_index + 1..=current_index]
 }
 
-fn combine_split_part_number(split_number: &Vec<String>, index_of_digit_in_number: usize) -> u32 {
-    let digits_after_index =
-        combine_split_part_number_ascending(&split_number, index_of_digit_in_number);
-    let digits_before_index =
-        combine_split_part_number_descending(&split_number, index_of_digit_in_number);
-
-    format!(
-        "{digits_before_index}{}{digits_after_index}",
-        split_number[index_of_digit_in_number]
-    )
-    .parse::<u32>()
-    .unwrap()
+fn combine_split_part_number(split_number: &[u8], index_of_digit_in_number: usize) -> u32 {
+    let (start, end) = find_part_number_span(split_number, index_of_digit_in_number);
+    split_number[start..=end]
+        .iter()
+        .fold(0, |number, digit| number * 10 + (digit - b'0') as u32)
 }
 
 fn extract_indices_of_numbers_adjacent_to_symbol(
-    engine_schematic: &Vec<Vec<String>>,
+    engine_schematic: &[&str],
     symbol_index: (usize, usize),
 ) -> Vec<(usize, usize)> {
     let left_index = symbol_index.0.checked_sub(1);
@@ -124,28 +107,26 @@ fn extract_indices_of_numbers_adjacent_to_symbol(
         .filter(|x| {
             x.0.is_some()
                 && x.1.is_some()
-                && engine_schematic[x.1.unwrap()][x.0.unwrap()]
-                    .parse::<u32>()
-                    .is_ok()
+                && engine_schematic[x.1.unwrap()].as_bytes()[x.0.unwrap()].is_ascii_digit()
         })
         .map(|x| (x.0.unwrap(), x.1.unwrap()))
         .collect::<Vec<_>>()
 }
 
 fn find_part_indices_adjacent_to_symbol(
-    engine_schematic: &Vec<Vec<String>>,
+    engine_schematic: &[&str],
     find_gears: bool,
 ) -> Vec<(usize, usize)> {
     let mut part_locations = vec![];
     for (line_number, line) in engine_schematic.iter().enumerate() {
-        for (index, schematic_item) in line.iter().enumerate() {
-            if schematic_item.parse::<u32>().is_err() && schematic_item != &String::from(".") {
+        for (index, schematic_item) in line.bytes().enumerate() {
+            if !schematic_item.is_ascii_digit() && schematic_item != b'.' {
                 let mut indices_of_numbers = extract_indices_of_numbers_adjacent_to_symbol(
                     engine_schematic,
                     (index, line_number),
                 );
                 if find_gears {
-                    if schematic_item == &String::from("*") {
+                    if schematic_item == b'*' {
                         let deduplicated_indices_of_numbers =
                             deduplicate_part_indices(indices_of_numbers);
                         indices_of_numbers = if deduplicated_indices_of_numbers.len() == 2 {
@@ -189,12 +170,67 @@ fn deduplicate_part_indices(mut part_indices: Vec<(usize, usize)>) -> Vec<(usize
     part_indices
 }
 
-fn extract_part_numbers_to_sum(engine_schematic: &Vec<Vec<String>>, find_gears: bool) -> Vec<u32> {
+fn find_part_number_span(split_number: &[u8], index_of_digit_in_number: usize) -> (usize, usize) {
+    let digits_before_index =
+        combine_split_part_number_descending(split_number, index_of_digit_in_number);
+    let digits_after_index =
+        combine_split_part_number_ascending(split_number, index_of_digit_in_number);
+    (
+        index_of_digit_in_number - digits_before_index.len(),
+        index_of_digit_in_number + digits_after_index.len(),
+    )
+}
+
+fn visualize_engine_schematic(engine_schematic: &[&str], find_gears: bool) -> Vec<Frame> {
+    let mut frames = vec![];
+    for (line_number, line) in engine_schematic.iter().enumerate() {
+        for (index, schematic_item) in line.bytes().enumerate() {
+            if schematic_item.is_ascii_digit() || schematic_item == b'.' {
+                continue;
+            }
+            if find_gears && schematic_item != b'*' {
+                continue;
+            }
+            let part_indices =
+                deduplicate_part_indices(extract_indices_of_numbers_adjacent_to_symbol(
+                    engine_schematic,
+                    (index, line_number),
+                ));
+            if find_gears && part_indices.len() != 2 {
+                continue;
+            }
+            let mut highlighted_ranges = vec![vec![]; engine_schematic.len()];
+            highlighted_ranges[line_number].push((index, index));
+            let mut part_numbers = vec![];
+            for part_index in part_indices {
+                let split_number = engine_schematic[part_index.1].as_bytes();
+                highlighted_ranges[part_index.1]
+                    .push(find_part_number_span(split_number, part_index.0));
+                part_numbers.push(combine_split_part_number(split_number, part_index.0));
+            }
+            frames.push(Frame::new(
+                format!(
+                    "symbol {} at ({index}, {line_number}): parts {part_numbers:?}",
+                    char::from(schematic_item)
+                ),
+                engine_schematic
+                    .iter()
+                    .zip(&highlighted_ranges)
+                    .map(|(line, ranges)| highlight_ranges(line, ranges))
+                    .collect(),
+            ));
+        }
+    }
+    frames
+}
+
+fn extract_part_numbers_to_sum(engine_schematic: &[&str], find_gears: bool) -> Vec<u32> {
     let part_indices = find_part_indices_adjacent_to_symbol(engine_schematic, find_gears);
     let deduplicated_part_indices = deduplicate_part_indices(part_indices);
     let mut part_numbers_to_sum = vec![];
     for part_index in deduplicated_part_indices {
-        let part_number = combine_split_part_number(&engine_schematic[part_index.1], part_index.0);
+        let part_number =
+            combine_split_part_number(engine_schematic[part_index.1].as_bytes(), part_index.0);
         part_numbers_to_sum.push(part_number);
     }
     part_numbers_to_sum
@@ -206,36 +242,18 @@ mod tests {
 
     #[test]
     fn combine_split_part_number_descending_1() {
-        let expected = String::from("14");
-
-        let actual = combine_split_part_number_descending(
-            &vec![
-                String::from("1"),
-                String::from("4"),
-                String::from("6"),
-                String::from("7"),
-                String::from("5"),
-            ],
-            2,
-        );
+        let expected = b"14";
+
+        let actual = combine_split_part_number_descending(b"14675", 2);
 
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn combine_split_part_number_ascending_1() {
-        let expected = String::from("75");
-
-        let actual = combine_split_part_number_ascending(
-            &vec![
-                String::from("1"),
-                String::from("4"),
-                String::from("6"),
-                String::from("7"),
-                String::from("5"),
-            ],
-            2,
-        );
+        let expected = b"75";
+
+        let actual = combine_split_part_number_ascending(b"14675", 2);
 
         assert_eq!(expected, actual);
     }
@@ -244,58 +262,25 @@ mod tests {
     fn combine_split_part_number_1() {
         let expected: u32 = 14675;
 
-        let actual = combine_split_part_number(
-            &vec![
-                String::from("1"),
-                String::from("4"),
-                String::from("6"),
-                String::from("7"),
-                String::from("5"),
-            ],
-            2,
-        );
+        let actual = combine_split_part_number(b"14675", 2);
 
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn combine_split_part_number_descending_2() {
-        let expected = String::from("14");
-
-        let actual = combine_split_part_number_descending(
-            &vec![
-                String::from("."),
-                String::from("."),
-                String::from("."),
-                String::from("1"),
-                String::from("4"),
-                String::from("6"),
-                String::from("7"),
-                String::from("5"),
-            ],
-            5,
-        );
+        let expected = b"14";
+
+        let actual = combine_split_part_number_descending(b"...14675", 5);
 
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn combine_split_part_number_ascending_2() {
-        let expected = String::from("75");
-
-        let actual = combine_split_part_number_ascending(
-            &vec![
-                String::from("1"),
-                String::from("4"),
-                String::from("6"),
-                String::from("7"),
-                String::from("5"),
-                String::from("."),
-                String::from("."),
-                String::from("."),
-            ],
-            2,
-        );
+        let expected = b"75";
+
+        let actual = combine_split_part_number_ascending(b"14675...", 2);
 
         assert_eq!(expected, actual);
     }
@@ -304,24 +289,7 @@ mod tests {
     fn combine_split_part_number_2() {
         let expected: u32 = 14675;
 
-        let actual = combine_split_part_number(
-            &vec![
-                String::from("."),
-                String::from("."),
-                String::from("."),
-                String::from("."),
-                String::from("1"),
-                String::from("4"),
-                String::from("6"),
-                String::from("7"),
-                String::from("5"),
-                String::from("."),
-                String::from("."),
-                String::from("."),
-                String::from("."),
-            ],
-            6,
-        );
+        let actual = combine_split_part_number(b"....14675....", 6);
 
         assert_eq!(expected, actual);
     }
@@ -335,7 +303,6 @@ mod tests {
 ...*......
 ..35..633."
             .split("\n")
-            .map(|line| line.chars().map(String::from).collect::<Vec<String>>())
             .collect::<Vec<_>>();
         let actual = find_part_indices_adjacent_to_symbol(&input, false);
 
@@ -367,7 +334,6 @@ mod tests {
 ...$.*....
 .664.598.."
             .split("\n")
-            .map(|line| line.chars().map(String::from).collect::<Vec<String>>())
             .collect::<Vec<_>>();
         let actual = extract_part_numbers_to_sum(&input, false);
 
@@ -390,10 +356,36 @@ mod tests {
 ...$.*....
 .664.598.."
             .split("\n")
-            .map(|line| line.chars().map(String::from).collect::<Vec<String>>())
             .collect::<Vec<_>>();
         let actual = extract_part_numbers_to_sum(&input, true);
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn find_part_number_span_1() {
+        let expected = (2, 4);
+
+        let actual = find_part_number_span(b"..467...", 3);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn visualize_engine_schematic_gears() {
+        let expected = vec![String::from("symbol * at (1, 1): parts [12, 34]")];
+
+        let input = "\
+12.
+.*.
+.34"
+        .split("\n")
+        .collect::<Vec<_>>();
+        let actual = visualize_engine_schematic(&input, true)
+            .into_iter()
+            .map(|frame| frame.caption)
+            .collect::<Vec<_>>();
+
+        assert_eq!(expected, actual);
+    }
 }
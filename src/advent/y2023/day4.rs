@@ -1,18 +1,22 @@
-use std::{collections::HashSet, error::Error, fs};
+use std::{collections::HashSet, error::Error};
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{AdventDay, Parse, Trace};
 
 pub struct NewDay4Puzzle {}
 
+#[derive(Debug, Serialize)]
 pub struct Day4Puzzle {
     parsed_input: Vec<Card>,
 }
 
 impl Parse for NewDay4Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path)?;
-
-        let parsed_input = parse_input_into_cards(&puzzle_input);
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
+        let parsed_input = parse_input_into_cards(puzzle_input);
 
         Ok(Box::new(Day4Puzzle { parsed_input }))
     }
@@ -28,7 +32,7 @@ fn parse_numbers_into_hashset(numbers: &str) -> HashSet<u32> {
 }
 
 fn parse_card_components(card_line: &str) -> (&str, &str) {
-    let mut split_card_line = card_line.split(": ").map(|x| x.split(" | ")).flatten();
+    let mut split_card_line = card_line.split(": ").flat_map(|x| x.split(" | "));
     split_card_line.next();
     let winning_numbers = split_card_line.next().unwrap();
     let card_numbers = split_card_line.next().unwrap();
@@ -48,16 +52,16 @@ fn parse_input_into_cards(input: &str) -> Vec<Card> {
 }
 
 impl AdventDay for Day4Puzzle {
-    fn solve_first_puzzle(&self) -> String {
+    fn solve_first_puzzle(&self, _trace: &mut Trace) -> String {
         calculate_sum_of_card_worths(&self.parsed_input).to_string()
     }
 
-    fn solve_second_puzzle(&self) -> String {
-        calculate_count_of_cards(&self.parsed_input).to_string()
+    fn solve_second_puzzle(&self, _trace: &mut Trace) -> Option<String> {
+        Some(calculate_count_of_cards(&self.parsed_input).to_string())
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 struct Card {
     winning_numbers: HashSet<u32>,
     card_numbers: HashSet<u32>,
@@ -89,7 +93,7 @@ impl Card {
     }
 }
 
-fn calculate_sum_of_card_worths(cards: &Vec<Card>) -> u32 {
+fn calculate_sum_of_card_worths(cards: &[Card]) -> u32 {
     cards
         .iter()
         .map(Card::calculate_worth)
@@ -97,12 +101,12 @@ fn calculate_sum_of_card_worths(cards: &Vec<Card>) -> u32 {
         .unwrap()
 }
 
-fn calculate_count_of_cards(cards: &Vec<Card>) -> u32 {
+fn calculate_count_of_cards(cards: &[Card]) -> u32 {
     let mut card_counts = vec![1; cards.len()];
     for (index, card) in cards.iter().enumerate() {
         let card_matches = card.calculate_matches();
         for j in 1..card_matches + 1 {
-            card_counts[index + j as usize] += 1 * card_counts[index];
+            card_counts[index + j as usize] += card_counts[index];
         }
     }
     card_counts.into_iter().reduce(|acc, e| acc + e).unwrap()
@@ -1,20 +1,24 @@
 // Please don't look at this solution, I'm so sorry for writing this
-use std::{error::Error, fs};
+use std::error::Error;
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{AdventDay, Parse, Trace};
 
 pub struct NewDay5Puzzle {}
 
+#[derive(Debug, Serialize)]
 pub struct Day5Puzzle {
     seeds: Vec<u64>,
     conversion_rules: Vec<Vec<AlmanacConversion>>,
 }
 
 impl Parse for NewDay5Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path)?;
-
-        let (seeds, conversion_rules) = split_input_into_sections(&puzzle_input);
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
+        let (seeds, conversion_rules) = split_input_into_sections(puzzle_input);
 
         Ok(Box::new(Day5Puzzle {
             seeds,
@@ -63,13 +67,15 @@ fn split_input_into_sections(input: &str) -> (Vec<u64>, Vec<Vec<AlmanacConversio
 }
 
 impl AdventDay for Day5Puzzle {
-    fn solve_first_puzzle(&self) -> String {
+    fn solve_first_puzzle(&self, _trace: &mut Trace) -> String {
         find_minimum_location_from_seeds(&self.conversion_rules, &self.seeds).to_string()
     }
 
-    fn solve_second_puzzle(&self) -> String {
+    fn solve_second_puzzle(&self, _trace: &mut Trace) -> Option<String> {
         // The commented out part of this function solved the puzzle but took 4 hours
-        find_minimum_location_from_seed_ranges(&self.conversion_rules, &self.seeds).to_string()
+        Some(
+            find_minimum_location_from_seed_ranges(&self.conversion_rules, &self.seeds).to_string(),
+        )
         //     let mut expanded_seeds = vec![];
         //     for (index, seed_range_start) in self.seeds.iter().enumerate() {
         //         if index % 2 != 0 {
@@ -83,7 +89,7 @@ impl AdventDay for Day5Puzzle {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 struct AlmanacConversion {
     source_range_start: u64,
     destination_range_start: u64,
@@ -102,7 +108,7 @@ impl AlmanacConversion {
     fn is_number_in_source_range(&self, source_category_number: u64) -> bool {
         let conversion_rule_source_range =
             self.source_range_start..self.source_range_start + self.range;
-        return conversion_rule_source_range.contains(&source_category_number);
+        conversion_rule_source_range.contains(&source_category_number)
     }
 
     fn convert_source_number_to_dest_number(&self, source_category_number: u64) -> u64 {
@@ -152,15 +158,14 @@ impl AlmanacConversion {
         }
         let new_range = source_category_range_end - self.source_range_start + 1;
         let undershoot = self.source_range_start - source_category_range.0;
-        return (
+        (
             (self.destination_range_start, new_range),
             (source_category_range.0, undershoot),
-        );
+        )
     }
 
     fn convert_source_category_range_containing_source_range(&self) -> (u64, u64) {
-        let converted_range = (self.destination_range_start, self.range);
-        converted_range
+        (self.destination_range_start, self.range)
     }
 }
 
@@ -178,7 +183,7 @@ fn convert_between_almanac_categories(
 
 fn find_minimum_location_from_seeds(
     almanac_conversions: &Vec<Vec<AlmanacConversion>>,
-    seeds: &Vec<u64>,
+    seeds: &[u64],
 ) -> u64 {
     seeds
         .iter()
@@ -186,7 +191,7 @@ fn find_minimum_location_from_seeds(
             let mut converted_value = *x;
             for almanac_conversion in almanac_conversions {
                 converted_value =
-                    convert_between_almanac_categories(&almanac_conversion, converted_value)
+                    convert_between_almanac_categories(almanac_conversion, converted_value)
             }
             converted_value
         })
@@ -194,7 +199,7 @@ fn find_minimum_location_from_seeds(
         .unwrap()
 }
 
-fn extract_seed_ranges_from_seeds(seeds: &Vec<u64>) -> Vec<(u64, u64)> {
+fn extract_seed_ranges_from_seeds(seeds: &[u64]) -> Vec<(u64, u64)> {
     let mut seed_ranges = vec![];
     for (index, seed_range_start) in seeds.iter().enumerate() {
         if index % 2 != 0 {
@@ -252,12 +257,12 @@ fn convert_range_between_almanac_categories(
 
 fn find_minimum_location_from_seed_ranges(
     almanac_conversions: &Vec<Vec<AlmanacConversion>>,
-    seeds: &Vec<u64>,
+    seeds: &[u64],
 ) -> u64 {
     let initial_seed_ranges = extract_seed_ranges_from_seeds(seeds);
     initial_seed_ranges
         .iter()
-        .map(|x| {
+        .flat_map(|x| {
             let mut converted_ranges = vec![*x];
             for almanac_conversion in almanac_conversions {
                 converted_ranges =
@@ -265,7 +270,6 @@ fn find_minimum_location_from_seed_ranges(
             }
             converted_ranges
         })
-        .flatten()
         .map(|x| x.0)
         .min()
         .unwrap()
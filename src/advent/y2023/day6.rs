@@ -1,18 +1,22 @@
-use std::{error::Error, fs};
+use std::error::Error;
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{AdventDay, Parse, Trace};
 
 pub struct NewDay6Puzzle {}
 
+#[derive(Debug, Serialize)]
 pub struct Day6Puzzle {
     allocated_times: Vec<u64>,
     distance_records: Vec<u64>,
 }
 
 impl Parse for NewDay6Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path)?;
-
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
         let mut split_lines = puzzle_input.split("\n");
         let allocated_times = parse_input_line(split_lines.next().unwrap());
         let distance_records = parse_input_line(split_lines.next().unwrap());
@@ -36,11 +40,11 @@ fn parse_input_line(input_line: &str) -> Vec<u64> {
 }
 
 impl AdventDay for Day6Puzzle {
-    fn solve_first_puzzle(&self) -> String {
+    fn solve_first_puzzle(&self, _trace: &mut Trace) -> String {
         calculate_margin_of_error(&self.allocated_times, &self.distance_records).to_string()
     }
 
-    fn solve_second_puzzle(&self) -> String {
+    fn solve_second_puzzle(&self, _trace: &mut Trace) -> Option<String> {
         let single_time = self
             .allocated_times
             .iter()
@@ -55,7 +59,10 @@ impl AdventDay for Day6Puzzle {
             .collect::<String>()
             .parse::<u64>()
             .unwrap();
-        calculate_number_of_different_winning_charge_times(single_record, single_time).to_string()
+        Some(
+            calculate_number_of_different_winning_charge_times(single_record, single_time)
+                .to_string(),
+        )
     }
 }
 
@@ -95,7 +102,7 @@ fn calculate_number_of_different_winning_charge_times(
     highest_charge_time - lowest_charge_time + 1
 }
 
-fn calculate_margin_of_error(allocated_times: &Vec<u64>, distance_records: &Vec<u64>) -> u64 {
+fn calculate_margin_of_error(allocated_times: &[u64], distance_records: &[u64]) -> u64 {
     allocated_times
         .iter()
         .enumerate()
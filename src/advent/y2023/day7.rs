@@ -1,6 +1,8 @@
-use std::{collections::HashMap, error::Error, fs, str::FromStr};
+use std::{collections::HashMap, error::Error, str::FromStr};
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{AdventDay, Parse, Trace};
 
 const FIVE_OF_A_KIND_SCORE: u32 = 10000000;
 const FOUR_OF_A_KIND_SCORE: u32 = 9000000;
@@ -12,22 +14,19 @@ const HIGH_CARD_SCORE: u32 = 4000000;
 
 pub struct NewDay7Puzzle {}
 
+#[derive(Debug, Serialize)]
 pub struct Day7Puzzle {
-    part_one_parsed_input: Vec<Hand>,
-    part_two_parsed_input: Vec<Hand>,
+    parsed_input: Vec<DealtHand>,
 }
 
 impl Parse for NewDay7Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path)?;
-
-        let part_one_parsed_input = parse_hands(&puzzle_input, false);
-        let part_two_parsed_input = parse_hands(&puzzle_input, true);
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
+        let parsed_input = parse_dealt_hands(puzzle_input);
 
-        Ok(Box::new(Day7Puzzle {
-            part_one_parsed_input,
-            part_two_parsed_input,
-        }))
+        Ok(Box::new(Day7Puzzle { parsed_input }))
     }
 }
 
@@ -59,10 +58,10 @@ fn calculate_hand_score(char_counts: &HashMap<char, u32>) -> u32 {
     for char_count in char_counts.values() {
         *pair_counts.entry(*char_count).or_insert(0) += 1;
     }
-    if let Some(_) = pair_counts.get(&5) {
+    if pair_counts.contains_key(&5) {
         return FIVE_OF_A_KIND_SCORE;
     }
-    if let Some(_) = pair_counts.get(&4) {
+    if pair_counts.contains_key(&4) {
         return FOUR_OF_A_KIND_SCORE;
     }
     let pair_count_keys = pair_counts.keys().collect::<Vec<_>>();
@@ -100,7 +99,7 @@ fn calculate_hand_score_with_jokers(mut char_counts: HashMap<char, u32>) -> u32
     hand_score
 }
 
-fn calculate_character_position_additional_score(hand: &Vec<Card>, part_two: bool) -> u32 {
+fn calculate_character_position_additional_score(hand: &[Card], part_two: bool) -> u32 {
     let mut additional_score = 0;
     let position_multiplier = HashMap::from([(1, 1), (2, 15), (3, 211), (4, 2955), (5, 41370)]);
     for (i, card) in hand.iter().rev().enumerate() {
@@ -113,46 +112,91 @@ fn calculate_character_position_additional_score(hand: &Vec<Card>, part_two: boo
     additional_score
 }
 
-fn parse_hands(hands_str: &str, part_two: bool) -> Vec<Hand> {
-    let mut hands = hands_str
+fn parse_dealt_hands(hands_str: &str) -> Vec<DealtHand> {
+    hands_str
         .split("\n")
         .map(|x| {
             let (hand, bid) = parse_hand_and_bid(x);
-            let cards = parse_cards_in_hand(hand);
-            let char_counts = extract_character_counts_from_hand(hand);
-            let hand_score: u32;
-            if part_two && char_counts.get(&'J').is_some() {
-                hand_score = calculate_hand_score_with_jokers(char_counts);
-            } else {
-                hand_score = calculate_hand_score(&char_counts);
-            }
-            let card_position_additional_score =
-                calculate_character_position_additional_score(&cards, part_two);
-            Hand {
-                cards,
-                value: hand_score + card_position_additional_score,
+            DealtHand {
+                cards: parse_cards_in_hand(hand),
+                char_counts: extract_character_counts_from_hand(hand),
                 bid: bid.parse::<u32>().unwrap(),
             }
         })
+        .collect()
+}
+
+fn score_hand(dealt_hand: &DealtHand, hand_score: u32, part_two: bool) -> Hand {
+    let card_position_additional_score =
+        calculate_character_position_additional_score(&dealt_hand.cards, part_two);
+    Hand {
+        cards: dealt_hand.cards.clone(),
+        value: hand_score + card_position_additional_score,
+        bid: dealt_hand.bid,
+    }
+}
+
+fn score_hands(dealt_hands: &[DealtHand], part_two: bool) -> Vec<Hand> {
+    let mut hands = dealt_hands
+        .iter()
+        .map(|dealt_hand| {
+            let hand_score = if part_two && dealt_hand.char_counts.contains_key(&'J') {
+                calculate_hand_score_with_jokers(dealt_hand.char_counts.clone())
+            } else {
+                calculate_hand_score(&dealt_hand.char_counts)
+            };
+            score_hand(dealt_hand, hand_score, part_two)
+        })
         .collect::<Vec<_>>();
     hands.sort();
     hands
 }
 
 impl AdventDay for Day7Puzzle {
-    fn solve_first_puzzle(&self) -> String {
-        calculate_total_winnings(&self.part_one_parsed_input).to_string()
+    fn solve_first_puzzle(&self, _trace: &mut Trace) -> String {
+        calculate_total_winnings(&score_hands(&self.parsed_input, false)).to_string()
+    }
+
+    fn solve_second_puzzle(&self, _trace: &mut Trace) -> Option<String> {
+        Some(calculate_total_winnings(&score_hands(&self.parsed_input, true)).to_string())
     }
 
-    fn solve_second_puzzle(&self) -> String {
-        calculate_total_winnings(&self.part_two_parsed_input).to_string()
+    /// Hands without jokers score the same in both parts, so each is only scored again
+    /// when it holds a joker.
+    fn solve_both_puzzles(
+        &self,
+        _first_trace: &mut Trace,
+        _second_trace: &mut Trace,
+    ) -> Option<(String, String)> {
+        let (mut part_one_hands, mut part_two_hands): (Vec<_>, Vec<_>) = self
+            .parsed_input
+            .iter()
+            .map(|dealt_hand| {
+                let hand_score = calculate_hand_score(&dealt_hand.char_counts);
+                let hand_score_with_jokers = if dealt_hand.char_counts.contains_key(&'J') {
+                    calculate_hand_score_with_jokers(dealt_hand.char_counts.clone())
+                } else {
+                    hand_score
+                };
+                (
+                    score_hand(dealt_hand, hand_score, false),
+                    score_hand(dealt_hand, hand_score_with_jokers, true),
+                )
+            })
+            .unzip();
+        part_one_hands.sort();
+        part_two_hands.sort();
+        Some((
+            calculate_total_winnings(&part_one_hands).to_string(),
+            calculate_total_winnings(&part_two_hands).to_string(),
+        ))
     }
 }
 
 #[derive(Debug, PartialEq)]
 struct ParseCardError;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 struct Card(u32);
 
 impl FromStr for Card {
@@ -178,14 +222,22 @@ impl FromStr for Card {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// A hand as dealt, before it is scored under either part's rules.
+#[derive(Debug, Serialize)]
+struct DealtHand {
+    cards: Vec<Card>,
+    char_counts: HashMap<char, u32>,
+    bid: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 struct Hand {
     value: u32,
     cards: Vec<Card>,
     bid: u32,
 }
 
-fn calculate_total_winnings(hands: &Vec<Hand>) -> u32 {
+fn calculate_total_winnings(hands: &[Hand]) -> u32 {
     hands
         .iter()
         .enumerate()
@@ -197,6 +249,7 @@ fn calculate_total_winnings(hands: &Vec<Hand>) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::advent::TraceLevel;
 
     #[test]
     fn extract_character_counts_from_hand_1() {
@@ -287,7 +340,7 @@ mod tests {
     }
 
     #[test]
-    fn parse_hands_1() {
+    fn score_hands_1() {
         let expected: Vec<Hand> = vec![
             Hand {
                 value: 5132188,
@@ -322,13 +375,13 @@ T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483";
-        let actual = parse_hands(input, false);
+        let actual = score_hands(&parse_dealt_hands(input), false);
 
         assert_eq!(expected, actual)
     }
 
     #[test]
-    fn parse_hands_2() {
+    fn score_hands_2() {
         let expected: Vec<Hand> = vec![
             Hand {
                 value: 5132188,
@@ -387,7 +440,7 @@ QQQJA 483
 AAJJA 235
 JJJJJ 91
 7A772 167";
-        let actual = parse_hands(input, true);
+        let actual = score_hands(&parse_dealt_hands(input), true);
 
         assert_eq!(expected, actual)
     }
@@ -402,7 +455,7 @@ T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483";
-        let input = parse_hands(hands_str, false);
+        let input = score_hands(&parse_dealt_hands(hands_str), false);
         let actual = calculate_total_winnings(&input);
 
         assert_eq!(expected, actual);
@@ -418,9 +471,28 @@ T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483";
-        let input = parse_hands(hands_str, true);
+        let input = score_hands(&parse_dealt_hands(hands_str), true);
         let actual = calculate_total_winnings(&input);
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn solve_both_puzzles_1() {
+        let expected = Some((String::from("6440"), String::from("5905")));
+
+        let input = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+        let advent_day = NewDay7Puzzle {}.parse_input(input).unwrap();
+        let actual = advent_day.solve_both_puzzles(
+            &mut Trace::new(TraceLevel::Off),
+            &mut Trace::new(TraceLevel::Off),
+        );
+
+        assert_eq!(expected, actual);
+    }
 }
@@ -1,18 +1,22 @@
-use std::{collections::HashMap, error::Error, fs};
+use std::{collections::HashMap, error::Error};
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{AdventDay, Parse, Trace, TraceLevel};
 
 pub struct NewDay8Puzzle {}
 
-pub struct Day8Puzzle {
+#[derive(Debug, Serialize)]
+pub struct Day8Puzzle<'a> {
     moves: Vec<LeftRight>,
-    node_map: HashMap<String, (String, String)>,
+    node_map: HashMap<&'a str, (&'a str, &'a str)>,
 }
 
 impl Parse for NewDay8Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path)?;
-
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
         let mut moves_and_node_map = puzzle_input.split("\n\n");
 
         let moves_str = moves_and_node_map.next().unwrap();
@@ -32,17 +36,13 @@ fn parse_moves(left_or_right_string: &str) -> Vec<LeftRight> {
         .collect::<Vec<_>>()
 }
 
-fn parse_node(node_str: &str) -> (String, (String, String)) {
+fn parse_node(node_str: &str) -> (&str, (&str, &str)) {
     let mut node_key_and_directions = node_str.split(" = ");
     let node_key = node_key_and_directions.next().unwrap();
     let node_directions_str = node_key_and_directions.next().unwrap();
-    let mut node_directions = node_directions_str.split(", ").map(|x| {
-        x.chars()
-            .filter(|x| x != &'(' && x != &')')
-            .collect::<String>()
-    });
+    let mut node_directions = node_directions_str.trim_matches(['(', ')']).split(", ");
     (
-        String::from(node_key),
+        node_key,
         (
             node_directions.next().unwrap(),
             node_directions.next().unwrap(),
@@ -50,7 +50,7 @@ fn parse_node(node_str: &str) -> (String, (String, String)) {
     )
 }
 
-fn parse_nodes_map(nodes_str: &str) -> HashMap<String, (String, String)> {
+fn parse_nodes_map(nodes_str: &str) -> HashMap<&str, (&str, &str)> {
     let mut nodes_map = HashMap::new();
     nodes_str.split("\n").for_each(|x| {
         let parsed_node = parse_node(x);
@@ -59,16 +59,17 @@ fn parse_nodes_map(nodes_str: &str) -> HashMap<String, (String, String)> {
     nodes_map
 }
 
-impl AdventDay for Day8Puzzle {
-    fn solve_first_puzzle(&self) -> String {
-        count_moves_to_end_of_map(&self.moves, &self.node_map).to_string()
+impl AdventDay for Day8Puzzle<'_> {
+    fn solve_first_puzzle(&self, trace: &mut Trace) -> String {
+        count_moves_to_end_of_map(&self.moves, &self.node_map, trace).to_string()
     }
 
-    fn solve_second_puzzle(&self) -> String {
-        count_moves_to_end_of_map_as_ghost(&self.moves, &self.node_map).to_string()
+    fn solve_second_puzzle(&self, trace: &mut Trace) -> Option<String> {
+        Some(count_moves_to_end_of_map_as_ghost(&self.moves, &self.node_map, trace).to_string())
     }
 }
 
+#[derive(Debug, Serialize)]
 enum LeftRight {
     Left,
     Right,
@@ -84,8 +85,9 @@ impl LeftRight {
 }
 
 fn count_moves_to_end_of_map(
-    moves: &Vec<LeftRight>,
-    node_map: &HashMap<String, (String, String)>,
+    moves: &[LeftRight],
+    node_map: &HashMap<&str, (&str, &str)>,
+    trace: &mut Trace,
 ) -> u32 {
     let mut current_node = "AAA";
     let mut move_count = 0;
@@ -97,40 +99,63 @@ fn count_moves_to_end_of_map(
             current_move_index = 0;
         }
         match moves[current_move_index] {
-            LeftRight::Left => current_node = &possible_next_nodes.0,
-            LeftRight::Right => current_node = &possible_next_nodes.1,
+            LeftRight::Left => current_node = possible_next_nodes.0,
+            LeftRight::Right => current_node = possible_next_nodes.1,
         }
         move_count += 1;
         current_move_index += 1;
+        trace.record(TraceLevel::Steps, "node_visited", || {
+            vec![
+                ("node", current_node.to_string()),
+                ("move", move_count.to_string()),
+            ]
+        });
     }
     move_count
 }
 
 fn count_moves_to_end_of_map_as_ghost(
-    moves: &Vec<LeftRight>,
-    node_map: &HashMap<String, (String, String)>,
+    moves: &[LeftRight],
+    node_map: &HashMap<&str, (&str, &str)>,
+    trace: &mut Trace,
 ) -> u64 {
     let nodes_to_traverse = node_map
         .keys()
-        .filter(|x| x.chars().next_back().unwrap() == 'A')
+        .filter(|x| x.ends_with('A'))
+        .copied()
         .collect::<Vec<_>>();
     let mut current_lcm = 1;
 
     for mut node in nodes_to_traverse {
+        let start_node = node;
         let mut move_count = 0;
         let mut current_move_index = 0;
-        while node.chars().next_back().unwrap() != 'Z' {
+        while !node.ends_with('Z') {
             let possible_next_nodes = &node_map[node];
             if current_move_index == moves.len() {
                 current_move_index = 0;
             }
             match moves[current_move_index] {
-                LeftRight::Left => node = &possible_next_nodes.0,
-                LeftRight::Right => node = &possible_next_nodes.1,
+                LeftRight::Left => node = possible_next_nodes.0,
+                LeftRight::Right => node = possible_next_nodes.1,
             }
             move_count += 1;
             current_move_index += 1;
+            trace.record(TraceLevel::Detail, "node_visited", || {
+                vec![
+                    ("start", start_node.to_string()),
+                    ("node", node.to_string()),
+                    ("move", move_count.to_string()),
+                ]
+            });
         }
+        trace.record(TraceLevel::Steps, "ghost_reached_end", || {
+            vec![
+                ("start", start_node.to_string()),
+                ("end", node.to_string()),
+                ("moves", move_count.to_string()),
+            ]
+        });
         current_lcm = lcm(current_lcm, move_count)
     }
     current_lcm
@@ -162,7 +187,7 @@ AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
         let node_map = parse_nodes_map(nodes_map_str);
-        let actual = count_moves_to_end_of_map(&moves, &node_map);
+        let actual = count_moves_to_end_of_map(&moves, &node_map, &mut Trace::new(TraceLevel::Off));
 
         assert_eq!(expected, actual);
     }
@@ -182,7 +207,8 @@ ZZZ = (ZZZ, ZZZ)";
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
         let node_map = parse_nodes_map(nodes_map_str);
-        let actual = count_moves_to_end_of_map_as_ghost(&moves, &node_map);
+        let actual =
+            count_moves_to_end_of_map_as_ghost(&moves, &node_map, &mut Trace::new(TraceLevel::Off));
 
         assert_eq!(expected, actual);
     }
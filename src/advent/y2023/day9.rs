@@ -1,17 +1,21 @@
-use std::{error::Error, fs};
+use std::error::Error;
 
-use super::super::{AdventDay, Parse};
+use serde::Serialize;
+
+use super::super::{AdventDay, Parse, Trace, TraceLevel};
 
 pub struct NewDay9Puzzle {}
 
+#[derive(Debug, Serialize)]
 pub struct Day9Puzzle {
     parsed_input: Vec<Vec<i64>>,
 }
 
 impl Parse for NewDay9Puzzle {
-    fn parse_input(&self, input_path: &str) -> Result<Box<dyn AdventDay>, Box<dyn Error>> {
-        let puzzle_input = fs::read_to_string(input_path)?;
-
+    fn parse_input<'a>(
+        &self,
+        puzzle_input: &'a str,
+    ) -> Result<Box<dyn AdventDay + 'a>, Box<dyn Error>> {
         let parsed_input = puzzle_input
             .split("\n")
             .map(|line| {
@@ -26,22 +30,45 @@ impl Parse for NewDay9Puzzle {
 }
 
 impl AdventDay for Day9Puzzle {
-    fn solve_first_puzzle(&self) -> String {
+    fn solve_first_puzzle(&self, trace: &mut Trace) -> String {
         self.parsed_input
             .iter()
-            .map(|x| predict_value_of_sequence(x, PredictionDirection::Future))
+            .map(|x| predict_value_of_sequence(x, PredictionDirection::Future, trace))
             .reduce(|acc, e| acc + e)
             .unwrap()
             .to_string()
     }
 
-    fn solve_second_puzzle(&self) -> String {
-        self.parsed_input
+    fn solve_second_puzzle(&self, trace: &mut Trace) -> Option<String> {
+        let sum = self
+            .parsed_input
             .iter()
-            .map(|x| predict_value_of_sequence(x, PredictionDirection::Past))
+            .map(|x| predict_value_of_sequence(x, PredictionDirection::Past, trace))
             .reduce(|acc, e| acc + e)
-            .unwrap()
-            .to_string()
+            .unwrap();
+        Some(sum.to_string())
+    }
+
+    /// Both predictions extrapolate the same rows of differences, so they are built once.
+    fn solve_both_puzzles(
+        &self,
+        first_trace: &mut Trace,
+        second_trace: &mut Trace,
+    ) -> Option<(String, String)> {
+        let (mut future_total, mut past_total) = (0, 0);
+        for sequence in &self.parsed_input {
+            let sequences_of_differences =
+                calculate_sequences_of_differences(sequence, first_trace);
+            let future_value =
+                extrapolate_sequence(&sequences_of_differences, PredictionDirection::Future);
+            let past_value =
+                extrapolate_sequence(&sequences_of_differences, PredictionDirection::Past);
+            record_prediction(sequence, future_value, first_trace);
+            record_prediction(sequence, past_value, second_trace);
+            future_total += future_value;
+            past_total += past_value;
+        }
+        Some((future_total.to_string(), past_total.to_string()))
     }
 }
 
@@ -51,10 +78,19 @@ enum PredictionDirection {
 }
 
 fn predict_value_of_sequence(
-    sequence: &Vec<i64>,
+    sequence: &[i64],
     prediction_direction: PredictionDirection,
+    trace: &mut Trace,
 ) -> i64 {
-    let mut sequences_of_differences = vec![sequence.clone()];
+    let sequences_of_differences = calculate_sequences_of_differences(sequence, trace);
+    let predicted_value = extrapolate_sequence(&sequences_of_differences, prediction_direction);
+    record_prediction(sequence, predicted_value, trace);
+    predicted_value
+}
+
+/// The sequence followed by each row of differences down to the first all-zero row.
+fn calculate_sequences_of_differences(sequence: &[i64], trace: &mut Trace) -> Vec<Vec<i64>> {
+    let mut sequences_of_differences = vec![sequence.to_vec()];
     let mut sequence_of_differences_all_zero = false;
 
     while !sequence_of_differences_all_zero {
@@ -72,15 +108,22 @@ fn predict_value_of_sequence(
                 .push(most_recently_calculated_sequence_of_differences[i + 1] - x)
         }
         sequence_of_differences_all_zero = next_sequence_of_differences.iter().all(|x| x == &0);
+        trace.record(TraceLevel::Detail, "difference_row", || {
+            vec![("values", format!("{next_sequence_of_differences:?}"))]
+        });
         sequences_of_differences.push(next_sequence_of_differences);
     }
+    sequences_of_differences
+}
 
-    sequences_of_differences.reverse();
+fn extrapolate_sequence(
+    sequences_of_differences: &[Vec<i64>],
+    prediction_direction: PredictionDirection,
+) -> i64 {
     let mut predicted_values = vec![0];
-    for i in 0..sequences_of_differences.len() - 1 {
+    for next_sequence in sequences_of_differences.iter().rev().skip(1) {
         let next_sequence_predicated_value_difference =
             predicted_values[predicted_values.len() - 1];
-        let next_sequence = &sequences_of_differences[i + 1];
         match prediction_direction {
             PredictionDirection::Future => predicted_values.push(
                 next_sequence[next_sequence.len() - 1] + next_sequence_predicated_value_difference,
@@ -93,6 +136,15 @@ fn predict_value_of_sequence(
     predicted_values[predicted_values.len() - 1]
 }
 
+fn record_prediction(sequence: &[i64], predicted_value: i64, trace: &mut Trace) {
+    trace.record(TraceLevel::Steps, "prediction", || {
+        vec![
+            ("sequence", format!("{sequence:?}")),
+            ("value", predicted_value.to_string()),
+        ]
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,7 +154,29 @@ mod tests {
         let expected = 18;
 
         let input = vec![0, 3, 6, 9, 12, 15];
-        let actual = predict_value_of_sequence(&input, PredictionDirection::Future);
+        let actual = predict_value_of_sequence(
+            &input,
+            PredictionDirection::Future,
+            &mut Trace::new(TraceLevel::Off),
+        );
+
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn solve_both_puzzles_matches_each_part() {
+        let input = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+        let advent_day = NewDay9Puzzle {}.parse_input(input).unwrap();
+        let mut trace = Trace::new(TraceLevel::Off);
+        let expected = Some((
+            advent_day.solve_first_puzzle(&mut trace),
+            advent_day.solve_second_puzzle(&mut trace).unwrap(),
+        ));
+
+        let actual = advent_day.solve_both_puzzles(&mut trace, &mut Trace::new(TraceLevel::Off));
 
         assert_eq!(expected, actual)
     }
@@ -0,0 +1,212 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+    str::FromStr,
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use crate::advent::Frame;
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const MINIMUM_FRAME_DELAY: Duration = Duration::from_millis(10);
+const MAXIMUM_FRAME_DELAY: Duration = Duration::from_secs(5);
+const CONTROLS_HELP: &str =
+    "[enter] step  [p + enter] pause/resume  [+/- + enter] speed  [q + enter] quit";
+
+#[derive(Debug, PartialEq)]
+pub enum PlaybackCommand {
+    Step,
+    TogglePause,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl FromStr for PlaybackCommand {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "" | "n" => Ok(PlaybackCommand::Step),
+            "p" => Ok(PlaybackCommand::TogglePause),
+            "+" => Ok(PlaybackCommand::Faster),
+            "-" => Ok(PlaybackCommand::Slower),
+            "q" => Ok(PlaybackCommand::Quit),
+            _ => Err("Unrecognised playback command"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Player {
+    frame_count: usize,
+    current_frame: usize,
+    frame_delay: Duration,
+    paused: bool,
+    finished: bool,
+}
+
+impl Player {
+    fn new(frame_count: usize, frame_delay: Duration, paused: bool) -> Player {
+        Player {
+            frame_count,
+            current_frame: 0,
+            frame_delay,
+            paused,
+            finished: frame_count == 0,
+        }
+    }
+
+    fn advance(&mut self) {
+        if self.current_frame + 1 < self.frame_count {
+            self.current_frame += 1;
+        } else {
+            self.finished = true;
+        }
+    }
+
+    fn handle(&mut self, command: PlaybackCommand) {
+        match command {
+            PlaybackCommand::Step => {
+                self.paused = true;
+                self.advance();
+            }
+            PlaybackCommand::TogglePause => self.paused = !self.paused,
+            PlaybackCommand::Faster => {
+                self.frame_delay = (self.frame_delay / 2).max(MINIMUM_FRAME_DELAY)
+            }
+            PlaybackCommand::Slower => {
+                self.frame_delay = (self.frame_delay * 2).min(MAXIMUM_FRAME_DELAY)
+            }
+            PlaybackCommand::Quit => self.finished = true,
+        }
+    }
+}
+
+fn spawn_command_reader() -> Receiver<PlaybackCommand> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if let Ok(command) = line.parse() {
+                if sender.send(command).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+    receiver
+}
+
+fn draw_frame(player: &Player, frame: &Frame) -> Result<(), Box<dyn Error>> {
+    let mut stdout = io::stdout().lock();
+    write!(stdout, "{CLEAR_SCREEN}")?;
+    writeln!(
+        stdout,
+        "{} [{}/{}]{}",
+        frame.caption,
+        player.current_frame + 1,
+        player.frame_count,
+        if player.paused { " (paused)" } else { "" }
+    )?;
+    for line in &frame.lines {
+        writeln!(stdout, "{line}")?;
+    }
+    writeln!(stdout, "{CONTROLS_HELP}")?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Animates `frames` in the terminal, reading playback commands from stdin.
+pub fn play_frames(
+    frames: &[Frame],
+    frame_delay: Duration,
+    start_paused: bool,
+) -> Result<(), Box<dyn Error>> {
+    let commands = spawn_command_reader();
+    let mut player = Player::new(frames.len(), frame_delay, start_paused);
+    let mut stdin_open = true;
+
+    while !player.finished {
+        draw_frame(&player, &frames[player.current_frame])?;
+        if player.paused && stdin_open {
+            match commands.recv() {
+                Ok(command) => player.handle(command),
+                Err(_) => {
+                    stdin_open = false;
+                    player.paused = false;
+                }
+            }
+            continue;
+        }
+        match commands.recv_timeout(player.frame_delay) {
+            Ok(command) => player.handle(command),
+            Err(RecvTimeoutError::Timeout) => player.advance(),
+            Err(RecvTimeoutError::Disconnected) => {
+                stdin_open = false;
+                thread::sleep(player.frame_delay);
+                player.advance();
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_pauses_and_advances() {
+        let mut player = Player::new(3, Duration::from_millis(100), false);
+        player.handle(PlaybackCommand::Step);
+
+        assert_eq!(1, player.current_frame);
+        assert!(player.paused);
+    }
+
+    #[test]
+    fn advancing_past_last_frame_finishes() {
+        let mut player = Player::new(2, Duration::from_millis(100), false);
+        player.advance();
+        player.advance();
+
+        assert_eq!(1, player.current_frame);
+        assert!(player.finished);
+    }
+
+    #[test]
+    fn speed_changes_are_clamped() {
+        let mut player = Player::new(2, Duration::from_millis(15), false);
+        player.handle(PlaybackCommand::Faster);
+
+        assert_eq!(MINIMUM_FRAME_DELAY, player.frame_delay);
+
+        let mut player = Player::new(2, Duration::from_secs(4), false);
+        player.handle(PlaybackCommand::Slower);
+
+        assert_eq!(MAXIMUM_FRAME_DELAY, player.frame_delay);
+    }
+
+    #[test]
+    fn parse_playback_commands() {
+        let expected = vec![
+            PlaybackCommand::Step,
+            PlaybackCommand::TogglePause,
+            PlaybackCommand::Faster,
+            PlaybackCommand::Slower,
+            PlaybackCommand::Quit,
+        ];
+
+        let actual = ["", "p", "+", "-", "q"]
+            .iter()
+            .map(|x| x.parse::<PlaybackCommand>().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,437 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    error::Error,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+use tracing::info_span;
+
+use crate::advent::{letters, AdventDay, Parse, Trace, TraceLevel};
+use crate::{collect_puzzles, list_puzzles, YEARS_WITH_SOLUTIONS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    /// Both parts in order. Days with only one puzzle report part 2 as not available.
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// How both parts of a day are solved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Each part on its own, so each part can be timed.
+    Separate,
+    /// Both parts with the day's shared solver where it has one, as `solve_both` does.
+    Combined,
+}
+
+impl Strategy {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::Separate => "separate",
+            Strategy::Combined => "combined",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    Letters { decoded: String, picture: String },
+}
+
+impl Answer {
+    pub(crate) fn from_solver_output(output: String) -> Answer {
+        if let Ok(integer) = output.parse::<i128>() {
+            return Answer::Integer(integer);
+        }
+        if letters::is_letter_picture(&output) {
+            if let Ok(decoded) = letters::recognize_letters(&output) {
+                return Answer::Letters {
+                    decoded,
+                    picture: output,
+                };
+            }
+        }
+        Answer::Text(output)
+    }
+
+    /// The answer on a single line, the decoded letters standing in for picture answers.
+    pub fn to_line(&self) -> String {
+        match self {
+            Answer::Letters { decoded, .. } => decoded.clone(),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(integer) => write!(f, "{integer}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Letters { picture, .. } => write!(f, "{picture}"),
+        }
+    }
+}
+
+/// Both answers of a puzzle. `second` is `None` for days with only one part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Answers {
+    pub first: Answer,
+    pub second: Option<Answer>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AdventError {
+    YearNotImplemented { year: String },
+    YearNotBuilt { year: String },
+    DayNotImplemented { year: String, day: String },
+    InvalidInput { message: String },
+    SolverFailed { part: Part, message: String },
+    PartNotAvailable { part: Part },
+}
+
+impl fmt::Display for AdventError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdventError::YearNotImplemented { year } => {
+                write!(f, "Year {year} has not been implemented yet")
+            }
+            AdventError::YearNotBuilt { year } => write!(
+                f,
+                "Year {year} was not built into this binary, rebuild with the y{year} feature"
+            ),
+            AdventError::DayNotImplemented { year, day } => {
+                write!(f, "{day} has not been implemented for {year}")
+            }
+            AdventError::InvalidInput { message } => write!(f, "Invalid puzzle input: {message}"),
+            AdventError::SolverFailed { part, message } => {
+                write!(f, "Part {} failed: {message}", part.number())
+            }
+            AdventError::PartNotAvailable { part } => {
+                write!(f, "This day has no part {}", part.number())
+            }
+        }
+    }
+}
+
+impl Error for AdventError {}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+    String::from("Solver panicked")
+}
+
+thread_local! {
+    static CATCHING_PANICS: Cell<bool> = const { Cell::new(false) };
+}
+
+static QUIET_PANIC_HOOK: Once = Once::new();
+
+/// Runs `f`, turning a panic into its message without the panic hook printing it. The
+/// host's hook is wrapped once, behind a `Once`, and still sees panics on other threads
+/// and outside the library's calls.
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    QUIET_PANIC_HOOK.call_once(|| {
+        let host_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING_PANICS.with(Cell::get) {
+                host_hook(info);
+            }
+        }));
+    });
+    let was_catching = CATCHING_PANICS.with(|catching| catching.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING_PANICS.with(|catching| catching.set(was_catching));
+    result.map_err(panic_message)
+}
+
+/// A registered puzzle that can parse and solve inputs for its year and day.
+pub struct Puzzle {
+    year: &'static str,
+    day: &'static str,
+    parser: Box<dyn Parse>,
+}
+
+impl Puzzle {
+    pub fn year(&self) -> &'static str {
+        self.year
+    }
+
+    pub fn day(&self) -> &'static str {
+        self.day
+    }
+
+    /// Parses `puzzle_input`. Solvers that panic on malformed input are reported as
+    /// `InvalidInput`, without printing anything.
+    pub fn parse<'a>(&self, puzzle_input: &'a str) -> Result<ParsedPuzzle<'a>, AdventError> {
+        let _parse_span = info_span!("parse", year = self.year, day = self.day).entered();
+        match catch_panic(|| self.parser.parse_input(puzzle_input)) {
+            Ok(Ok(advent_day)) => Ok(ParsedPuzzle { advent_day }),
+            Ok(Err(err)) => Err(AdventError::InvalidInput {
+                message: err.to_string(),
+            }),
+            Err(message) => Err(AdventError::InvalidInput { message }),
+        }
+    }
+
+    pub fn solve(&self, puzzle_input: &str) -> Result<Answers, AdventError> {
+        self.parse(puzzle_input)?.solve_both()
+    }
+}
+
+/// An input that has been parsed once and can be solved any number of times. It may
+/// borrow from the input it was parsed from.
+pub struct ParsedPuzzle<'a> {
+    advent_day: Box<dyn AdventDay + 'a>,
+}
+
+impl ParsedPuzzle<'_> {
+    pub fn solve(&self, part: Part) -> Result<Answer, AdventError> {
+        self.solve_traced(part, &mut Trace::new(TraceLevel::Off))
+    }
+
+    /// Solves `part`, recording the solver's intermediate states in `trace`.
+    pub(crate) fn solve_traced(
+        &self,
+        part: Part,
+        trace: &mut Trace,
+    ) -> Result<Answer, AdventError> {
+        let _solve_span = info_span!("solve", part = part.number()).entered();
+        let output = catch_panic(|| match part {
+            Part::One => Some(self.advent_day.solve_first_puzzle(trace)),
+            Part::Two => self.advent_day.solve_second_puzzle(trace),
+        })
+        .map_err(|message| AdventError::SolverFailed { part, message })?
+        .ok_or(AdventError::PartNotAvailable { part })?;
+        Ok(Answer::from_solver_output(output))
+    }
+
+    /// Solves both parts with the day's combined solver. `None` when the day has none,
+    /// and the panic message when it panics.
+    pub(crate) fn solve_combined(
+        &self,
+        first_trace: &mut Trace,
+        second_trace: &mut Trace,
+    ) -> Option<Result<(Answer, Answer), String>> {
+        let _solve_span = info_span!("solve", part = "both").entered();
+        let combined = catch_panic(|| {
+            self.advent_day
+                .solve_both_puzzles(first_trace, second_trace)
+        });
+        match combined {
+            Ok(answers) => answers.map(|(first, second)| {
+                Ok((
+                    Answer::from_solver_output(first),
+                    Answer::from_solver_output(second),
+                ))
+            }),
+            Err(message) => Some(Err(message)),
+        }
+    }
+
+    /// Solves both parts together when the day shares work between them. If the combined
+    /// solver is missing or panics, each part is solved on its own so failures name a part.
+    pub fn solve_both(&self) -> Result<Answers, AdventError> {
+        let combined = self.solve_combined(
+            &mut Trace::new(TraceLevel::Off),
+            &mut Trace::new(TraceLevel::Off),
+        );
+        match combined {
+            Some(Ok((first, second))) => Ok(Answers {
+                first,
+                second: Some(second),
+            }),
+            _ => Ok(Answers {
+                first: self.solve(Part::One)?,
+                second: match self.solve(Part::Two) {
+                    Ok(second) => Some(second),
+                    Err(AdventError::PartNotAvailable { .. }) => None,
+                    Err(err) => return Err(err),
+                },
+            }),
+        }
+    }
+
+    /// The day's changeable parameters and their current values.
+    pub(crate) fn parameters(&self) -> Vec<(&'static str, u64)> {
+        self.advent_day.parameters()
+    }
+
+    pub(crate) fn set_parameter(&mut self, name: &str, value: u64) -> Result<(), String> {
+        self.advent_day.set_parameter(name, value)
+    }
+}
+
+/// Shows the parsed structure of the day.
+impl fmt::Debug for ParsedPuzzle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.advent_day.fmt(f)
+    }
+}
+
+pub fn puzzle(year: &str, day: &str) -> Result<Puzzle, AdventError> {
+    let mut puzzle_days = collect_puzzles();
+    let (year, mut days) = puzzle_days.remove_entry(year).ok_or_else(|| {
+        if YEARS_WITH_SOLUTIONS.contains(&year) {
+            AdventError::YearNotBuilt {
+                year: year.to_string(),
+            }
+        } else {
+            AdventError::YearNotImplemented {
+                year: year.to_string(),
+            }
+        }
+    })?;
+    let (day, parser) = days
+        .remove_entry(day)
+        .ok_or_else(|| AdventError::DayNotImplemented {
+            year: year.to_string(),
+            day: day.to_string(),
+        })?;
+    Ok(Puzzle { year, day, parser })
+}
+
+/// Every registered puzzle, ordered by year and then day.
+pub fn puzzles() -> Vec<Puzzle> {
+    let mut puzzle_days = collect_puzzles();
+    list_puzzles()
+        .into_iter()
+        .filter_map(|(year, day)| {
+            let parser = puzzle_days.get_mut(year)?.remove(day)?;
+            Some(Puzzle { year, day, parser })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "y2023")]
+    fn solve_returns_typed_answers() -> Result<(), AdventError> {
+        let expected = Answers {
+            first: Answer::Integer(114),
+            second: Some(Answer::Integer(2)),
+        };
+
+        let actual =
+            puzzle("2023", "day9")?.solve("0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45")?;
+
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "y2022")]
+    fn single_part_days_have_no_second_answer() -> Result<(), AdventError> {
+        let expected = Answers {
+            first: Answer::Text(String::from("1-")),
+            second: None,
+        };
+
+        let puzzle = puzzle("2022", "day25")?;
+        let parsed_puzzle = puzzle.parse("1=\n1")?;
+        let actual = parsed_puzzle.solve_both()?;
+
+        assert_eq!(expected, actual);
+        assert_eq!(
+            Err(AdventError::PartNotAvailable { part: Part::Two }),
+            parsed_puzzle.solve(Part::Two)
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "y2022")]
+    fn text_answers_are_kept_as_text() -> Result<(), AdventError> {
+        let expected = Answer::Text(String::from("CMZ"));
+
+        let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2";
+        let actual = puzzle("2022", "day5")?.parse(input)?.solve(Part::One)?;
+
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+
+    #[test]
+    fn picture_outputs_become_letter_answers() {
+        let picture =
+            String::from("#...#####.\n#...##....\n.#.#.###..\n..#..#....\n..#..#....\n..#..####.");
+        let expected = Answer::Letters {
+            decoded: String::from("YE"),
+            picture: picture.clone(),
+        };
+
+        let actual = Answer::from_solver_output(picture);
+
+        assert_eq!(expected, actual);
+        assert_eq!("YE", actual.to_line());
+    }
+
+    #[test]
+    #[cfg(feature = "y2023")]
+    fn unknown_year_and_day_are_distinguished() {
+        assert_eq!(
+            Some(AdventError::YearNotImplemented {
+                year: String::from("2015")
+            }),
+            puzzle("2015", "day1").err()
+        );
+        assert_eq!(
+            Some(AdventError::DayNotImplemented {
+                year: String::from("2023"),
+                day: String::from("day25")
+            }),
+            puzzle("2023", "day25").err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "y2022")]
+    fn invalid_input_is_an_error() {
+        let actual = puzzle("2022", "day1").and_then(|puzzle| puzzle.solve("not calories"));
+
+        assert!(matches!(actual, Err(AdventError::InvalidInput { .. })));
+    }
+
+    #[test]
+    fn caught_panics_become_messages() {
+        let actual = catch_panic(|| -> u8 { panic!("Invalid crate") });
+
+        assert_eq!(Err(String::from("Invalid crate")), actual);
+        assert!(!CATCHING_PANICS.with(Cell::get));
+    }
+
+    #[test]
+    fn puzzles_iterates_the_registry_in_order() {
+        let actual = puzzles()
+            .iter()
+            .map(|puzzle| (puzzle.year(), puzzle.day()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(list_puzzles(), actual);
+    }
+}
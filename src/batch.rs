@@ -0,0 +1,485 @@
+use std::{
+    error::Error,
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::api::{self, AdventError, Answer, Part, Puzzle, Strategy};
+use crate::cache::{AnswerCache, DayKey};
+use crate::extract::{answers_path, read_answers};
+use crate::history::{self, HistoryRecord, RunSource};
+use crate::inputs::{plaintext_path, read_puzzle_input};
+
+const MISMATCH_STYLE: &str = "\x1b[1;31m";
+const RESET_STYLE: &str = "\x1b[0m";
+const ANSWERS_EXTENSION: &str = "answers";
+const INPUT_SUFFIX: &str = ".txt";
+
+/// Whether an input path names several inputs, as a directory or a `*`/`?` pattern.
+pub fn is_multi_input(input_path: &str) -> bool {
+    input_path.contains(['*', '?']) || Path::new(input_path).is_dir()
+}
+
+/// Matches `name` against a pattern where `*` is any run of characters and `?` any one.
+fn matches_pattern(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => {
+            matches_pattern(&pattern[1..], name)
+                || (!name.is_empty() && matches_pattern(pattern, &name[1..]))
+        }
+        (Some('?'), Some(_)) => matches_pattern(&pattern[1..], &name[1..]),
+        (Some(expected), Some(actual)) if expected == actual => {
+            matches_pattern(&pattern[1..], &name[1..])
+        }
+        _ => false,
+    }
+}
+
+/// Whether a file in an input directory is a puzzle input, plain or encrypted.
+fn is_input_file_name(name: &str) -> bool {
+    plaintext_path(name).ends_with(INPUT_SUFFIX)
+}
+
+/// Keeps the files matching `pattern`, or the puzzle inputs without one, skipping answers
+/// files. An encrypted input is skipped when its plaintext is also kept.
+fn select_inputs(files: Vec<PathBuf>, pattern: Option<&[char]>) -> Vec<PathBuf> {
+    let mut inputs = files
+        .into_iter()
+        .filter(|file| {
+            let name = file
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let is_answers_file = file
+                .extension()
+                .is_some_and(|extension| extension == ANSWERS_EXTENSION);
+            match pattern {
+                Some(pattern) => {
+                    !is_answers_file && matches_pattern(pattern, &name.chars().collect::<Vec<_>>())
+                }
+                None => is_input_file_name(&name),
+            }
+        })
+        .collect::<Vec<_>>();
+    // Sorting puts `alice.txt` before `alice.txt.enc`, so the plaintext is kept.
+    inputs.sort();
+    inputs.dedup_by(|encrypted, plaintext| {
+        plaintext_path(&encrypted.to_string_lossy()) == plaintext.to_string_lossy()
+    });
+    inputs
+}
+
+/// Lists the inputs in a directory, or those matching a pattern in its last component.
+fn find_inputs(input_path: &str) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let path = Path::new(input_path);
+    let (directory, pattern) = if path.is_dir() {
+        (path, None)
+    } else {
+        let file_pattern = path
+            .file_name()
+            .ok_or("Input pattern must end in a file name")?
+            .to_string_lossy();
+        if path
+            .parent()
+            .is_some_and(|parent| parent.to_string_lossy().contains(['*', '?']))
+        {
+            return Err("Only the file name of an input pattern may contain * or ?".into());
+        }
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        (directory, Some(file_pattern.chars().collect::<Vec<_>>()))
+    };
+
+    let mut files = vec![];
+    for entry in fs::read_dir(directory)? {
+        let entry_path = entry?.path();
+        if entry_path.is_file() {
+            files.push(entry_path);
+        }
+    }
+    let inputs = select_inputs(files, pattern.as_deref());
+    if inputs.is_empty() {
+        return Err(format!("No inputs found at {input_path}").into());
+    }
+    Ok(inputs)
+}
+
+#[derive(Debug, PartialEq)]
+enum PartOutcome {
+    Solved { answer: String, duration: Duration },
+    Cached { answer: String },
+    Failed { message: String },
+}
+
+#[derive(Debug, PartialEq)]
+struct InputResult {
+    name: String,
+    parts: Vec<PartOutcome>,
+    expected_answers: Option<Vec<String>>,
+}
+
+impl InputResult {
+    /// Parts whose answer differs from the answers file, or that failed to solve.
+    fn is_mismatch(&self, part_index: usize) -> bool {
+        match (&self.parts[part_index], &self.expected_answers) {
+            (PartOutcome::Failed { .. }, _) => true,
+            (
+                PartOutcome::Solved { answer, .. } | PartOutcome::Cached { answer },
+                Some(expected_answers),
+            ) => expected_answers
+                .get(part_index)
+                .is_some_and(|expected| expected != answer),
+            (_, None) => false,
+        }
+    }
+
+    fn status(&self) -> &'static str {
+        if (0..self.parts.len()).any(|part_index| self.is_mismatch(part_index)) {
+            "MISMATCH"
+        } else if self.expected_answers.is_some() {
+            "ok"
+        } else {
+            "no answers file"
+        }
+    }
+}
+
+fn solve_input(
+    puzzle: &Puzzle,
+    input_path: &Path,
+    mut answer_cache: Option<&mut AnswerCache>,
+) -> InputResult {
+    let name = input_path.display().to_string();
+    let expected_answers = read_answers(&answers_path(&name));
+    let failed_input = |message: String| InputResult {
+        name: name.clone(),
+        parts: vec![
+            PartOutcome::Failed {
+                message: message.clone(),
+            },
+            PartOutcome::Failed { message },
+        ],
+        expected_answers: expected_answers.clone(),
+    };
+
+    let puzzle_input = match read_puzzle_input(&name) {
+        Ok(puzzle_input) => puzzle_input,
+        Err(err) => return failed_input(err.to_string()),
+    };
+    let day_key = DayKey::new(
+        puzzle.year(),
+        puzzle.day(),
+        Strategy::Separate,
+        &puzzle_input,
+    );
+    // Parsed on the first part missing from the cache, so cached inputs are not parsed.
+    let mut parsed_puzzle = None;
+    let parts = Part::ALL
+        .into_iter()
+        .filter_map(|part| {
+            let cached_answer = answer_cache
+                .as_deref()
+                .and_then(|answer_cache| answer_cache.get(&day_key, part.number()));
+            if let Some(cached_answer) = cached_answer {
+                return cached_answer.map(|cached_answer| PartOutcome::Cached {
+                    answer: Answer::from_solver_output(cached_answer.to_string()).to_line(),
+                });
+            }
+            let parsed_puzzle =
+                match parsed_puzzle.get_or_insert_with(|| puzzle.parse(&puzzle_input)) {
+                    Ok(parsed_puzzle) => parsed_puzzle,
+                    Err(err) => {
+                        return Some(PartOutcome::Failed {
+                            message: err.to_string(),
+                        })
+                    }
+                };
+            let start = Instant::now();
+            let answer = match parsed_puzzle.solve(part) {
+                Ok(answer) => Some(answer),
+                Err(AdventError::PartNotAvailable { .. }) => None,
+                Err(err) => {
+                    return Some(PartOutcome::Failed {
+                        message: err.to_string(),
+                    })
+                }
+            };
+            let duration = start.elapsed();
+            if let Some(answer_cache) = answer_cache.as_deref_mut() {
+                let cached_answer = answer.as_ref().map(Answer::to_string);
+                answer_cache.insert(&day_key, part.number(), cached_answer.as_deref());
+            }
+            answer.map(|answer| PartOutcome::Solved {
+                answer: answer.to_line(),
+                duration,
+            })
+        })
+        .collect();
+    InputResult {
+        name,
+        parts,
+        expected_answers,
+    }
+}
+
+/// The solved parts, as cached and failed parts were not timed.
+fn history_records(puzzle: &Puzzle, input_results: &[InputResult]) -> Vec<HistoryRecord> {
+    input_results
+        .iter()
+        .flat_map(|input_result| Part::ALL.into_iter().zip(&input_result.parts))
+        .filter_map(|(part, part_outcome)| match part_outcome {
+            PartOutcome::Solved { answer, duration } => Some(HistoryRecord::new(
+                puzzle.year(),
+                puzzle.day(),
+                part.number(),
+                *duration,
+                answer,
+                RunSource::Batch,
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+fn format_table(input_results: &[InputResult], highlight: bool) -> String {
+    let mut rows = vec![vec![
+        String::from("input"),
+        String::from("part 1"),
+        String::from("time"),
+        String::from("part 2"),
+        String::from("time"),
+        String::from("status"),
+    ]];
+    let mut mismatched_cells = vec![];
+    for (row_index, input_result) in input_results.iter().enumerate() {
+        let mut row = vec![input_result.name.clone()];
+        for (part_index, part_outcome) in input_result.parts.iter().enumerate() {
+            match part_outcome {
+                PartOutcome::Solved { answer, duration } => {
+                    row.push(answer.clone());
+                    row.push(format!("{duration:?}"));
+                }
+                PartOutcome::Cached { answer } => {
+                    row.push(answer.clone());
+                    row.push(String::from("cached"));
+                }
+                PartOutcome::Failed { .. } => {
+                    row.push(String::from("error"));
+                    row.push(String::from("-"));
+                }
+            }
+            if input_result.is_mismatch(part_index) {
+                mismatched_cells.push((row_index + 1, 1 + part_index * 2));
+            }
+        }
+        // Single-part days leave the part 2 columns empty.
+        while row.len() < 5 {
+            row.push(String::from("-"));
+        }
+        row.push(String::from(input_result.status()));
+        if input_result.status() == "MISMATCH" {
+            mismatched_cells.push((row_index + 1, row.len() - 1));
+        }
+        rows.push(row);
+    }
+
+    let column_widths = (0..rows[0].len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let mut lines = rows
+        .iter()
+        .enumerate()
+        .map(|(row_index, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(column, cell)| {
+                    let padding = " ".repeat(column_widths[column] - cell.chars().count());
+                    if highlight && mismatched_cells.contains(&(row_index, column)) {
+                        format!("{MISMATCH_STYLE}{cell}{RESET_STYLE}{padding}")
+                    } else {
+                        format!("{cell}{padding}")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    lines.extend(input_results.iter().flat_map(failure_lines));
+    lines.join("\n")
+}
+
+/// Explains each failed part under the table, once per input when parsing failed.
+fn failure_lines(input_result: &InputResult) -> Vec<String> {
+    let messages = input_result
+        .parts
+        .iter()
+        .map(|part_outcome| match part_outcome {
+            PartOutcome::Failed { message } => Some(message),
+            PartOutcome::Solved { .. } | PartOutcome::Cached { .. } => None,
+        })
+        .collect::<Vec<_>>();
+    if messages
+        .iter()
+        .all(|message| message.is_some() && *message == messages[0])
+    {
+        if let Some(message) = messages[0] {
+            return vec![format!("{}: {message}", input_result.name)];
+        }
+    }
+    messages
+        .iter()
+        .enumerate()
+        .filter_map(|(part_index, message)| {
+            Some(format!(
+                "{} part {}: {}",
+                input_result.name,
+                part_index + 1,
+                (*message)?
+            ))
+        })
+        .collect()
+}
+
+/// Solves every input matched by `input_path` and prints a table of answers and times,
+/// checked against each input's answers file.
+pub fn run_many_inputs(
+    year_of_puzzle: &str,
+    day_to_run: &str,
+    input_path: &str,
+    use_cache: bool,
+) -> Result<(), Box<dyn Error>> {
+    let puzzle = api::puzzle(year_of_puzzle, day_to_run)?;
+    let mut answer_cache = use_cache.then(AnswerCache::open);
+    let input_results = find_inputs(input_path)?
+        .iter()
+        .map(|input| solve_input(&puzzle, input, answer_cache.as_mut()))
+        .collect::<Vec<_>>();
+    if let Some(answer_cache) = &answer_cache {
+        if let Err(err) = answer_cache.save() {
+            tracing::warn!("Failed to save the answer cache: {err}");
+        }
+    }
+    history::record_runs_or_warn(&history_records(&puzzle, &input_results));
+
+    println!(
+        "{}",
+        format_table(&input_results, io::stdout().is_terminal())
+    );
+
+    if input_results
+        .iter()
+        .any(|input_result| input_result.status() == "MISMATCH")
+    {
+        return Err("Some inputs failed or did not match their answers".into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solved(answer: &str) -> PartOutcome {
+        PartOutcome::Solved {
+            answer: answer.to_string(),
+            duration: Duration::from_micros(5),
+        }
+    }
+
+    #[test]
+    fn patterns_match_file_names() {
+        let matches = |pattern: &str, name: &str| {
+            matches_pattern(
+                &pattern.chars().collect::<Vec<_>>(),
+                &name.chars().collect::<Vec<_>>(),
+            )
+        };
+
+        assert!(matches("*.txt", "alice.txt"));
+        assert!(matches("day?-*", "day9-bob"));
+        assert!(!matches("*.txt", "alice.answers"));
+        assert!(!matches("day?", "day10"));
+    }
+
+    #[test]
+    fn each_input_is_selected_once() {
+        let files = [
+            "in/alice.txt.enc",
+            "in/alice.answers",
+            "in/alice.txt",
+            "in/bob.txt.enc",
+            "in/notes.md",
+        ]
+        .map(PathBuf::from)
+        .to_vec();
+
+        assert_eq!(
+            vec![
+                PathBuf::from("in/alice.txt"),
+                PathBuf::from("in/bob.txt.enc")
+            ],
+            select_inputs(files.clone(), None)
+        );
+        assert_eq!(
+            vec![
+                PathBuf::from("in/alice.txt.enc"),
+                PathBuf::from("in/bob.txt.enc")
+            ],
+            select_inputs(files, Some(&['*', '.', 'e', 'n', 'c']))
+        );
+    }
+
+    #[test]
+    fn table_flags_mismatched_answers() {
+        let expected = "\
+input      part 1  time  part 2  time  status
+alice.txt  114     5µs   2       5µs   ok
+bob.txt    \x1b[1;31m99\x1b[0m      5µs   \x1b[1;31merror\x1b[0m   -     \x1b[1;31mMISMATCH\x1b[0m
+carol.txt  1       5µs   2       5µs   no answers file
+dave.txt   2=-1=0  5µs   -       -     ok
+bob.txt part 2: Part 2 failed";
+
+        let input_results = vec![
+            InputResult {
+                name: String::from("alice.txt"),
+                parts: vec![solved("114"), solved("2")],
+                expected_answers: Some(vec![String::from("114"), String::from("2")]),
+            },
+            InputResult {
+                name: String::from("bob.txt"),
+                parts: vec![
+                    solved("99"),
+                    PartOutcome::Failed {
+                        message: String::from("Part 2 failed"),
+                    },
+                ],
+                expected_answers: Some(vec![String::from("100")]),
+            },
+            InputResult {
+                name: String::from("carol.txt"),
+                parts: vec![solved("1"), solved("2")],
+                expected_answers: None,
+            },
+            InputResult {
+                name: String::from("dave.txt"),
+                parts: vec![solved("2=-1=0")],
+                expected_answers: Some(vec![String::from("2=-1=0")]),
+            },
+        ];
+        let actual = format_table(&input_results, true);
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,341 @@
+use std::{
+    error::Error,
+    io::{self, IsTerminal},
+    time::{Duration, Instant},
+};
+
+use crate::api::{self, Puzzle};
+use crate::history::{self, HistoryRecord, RunSource, BOTH_PARTS};
+use crate::inputs::{collection_input_path, read_puzzle_input};
+use crate::profiles::load_profile;
+
+const DEFAULT_INPUTS_DIRECTORY: &str = "inputs";
+/// The classic challenge: every day together in under a second.
+const DEFAULT_TOTAL_BUDGET: Duration = Duration::from_secs(1);
+const BAR_WIDTH: usize = 40;
+const OVER_BUDGET_STYLE: &str = "\x1b[1;31m";
+const RESET_STYLE: &str = "\x1b[0m";
+
+pub struct BudgetParams {
+    pub inputs_directory: String,
+    pub total_budget: Duration,
+    pub day_budget: Option<Duration>,
+    /// Takes the inputs directory from this profile instead.
+    pub profile: Option<String>,
+}
+
+/// `None` unless the budget is a positive number of milliseconds a `Duration` can hold,
+/// which rules out NaN, infinities and values such as `1e400`.
+fn parse_milliseconds(milliseconds: Option<String>) -> Option<Duration> {
+    milliseconds?
+        .parse::<f64>()
+        .ok()
+        .filter(|milliseconds| *milliseconds > 0.0)
+        .and_then(|milliseconds| Duration::try_from_secs_f64(milliseconds / 1000.0).ok())
+}
+
+impl BudgetParams {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<BudgetParams, &'static str> {
+        let mut inputs_directory = String::from(DEFAULT_INPUTS_DIRECTORY);
+        let mut total_budget = DEFAULT_TOTAL_BUDGET;
+        let mut day_budget = None;
+        let mut profile = None;
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "--inputs" => {
+                    inputs_directory = args
+                        .next()
+                        .ok_or("Path to inputs directory not provided after --inputs!")?;
+                }
+                "--total" => {
+                    total_budget = parse_milliseconds(args.next())
+                        .ok_or("Total budget must be a positive number of milliseconds!")?;
+                }
+                "--per-day" => {
+                    day_budget = Some(
+                        parse_milliseconds(args.next())
+                            .ok_or("Per-day budget must be a positive number of milliseconds!")?,
+                    );
+                }
+                "--profile" => {
+                    profile = Some(
+                        args.next()
+                            .ok_or("Profile name not provided after --profile!")?,
+                    );
+                }
+                _ => return Err("Unrecognised flag provided!"),
+            }
+        }
+
+        Ok(BudgetParams {
+            inputs_directory,
+            total_budget,
+            day_budget,
+            profile,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum DayOutcome {
+    Timed(Duration),
+    Failed(String),
+    NoInput,
+}
+
+#[derive(Debug, PartialEq)]
+struct DayTiming {
+    year: &'static str,
+    day: &'static str,
+    outcome: DayOutcome,
+    /// Kept to record once every day is timed, so writing it does not slow the next day.
+    history_record: Option<HistoryRecord>,
+}
+
+/// Times parsing and solving every part, as a full run of the day would.
+fn time_day(puzzle: &Puzzle, inputs_directory: &str) -> DayTiming {
+    let mut history_record = None;
+    let outcome = match collection_input_path(inputs_directory, puzzle.year(), puzzle.day()) {
+        None => DayOutcome::NoInput,
+        Some(input_path) => match read_puzzle_input(&input_path) {
+            Err(err) => DayOutcome::Failed(err.to_string()),
+            Ok(puzzle_input) => {
+                let start = Instant::now();
+                match puzzle
+                    .parse(&puzzle_input)
+                    .and_then(|parsed_puzzle| parsed_puzzle.solve_both())
+                {
+                    Ok(answers) => {
+                        let duration = start.elapsed();
+                        let answers = [Some(answers.first), answers.second]
+                            .into_iter()
+                            .flatten()
+                            .map(|answer| answer.to_string())
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        history_record = Some(HistoryRecord::new(
+                            puzzle.year(),
+                            puzzle.day(),
+                            BOTH_PARTS,
+                            duration,
+                            &answers,
+                            RunSource::Budget,
+                        ));
+                        DayOutcome::Timed(duration)
+                    }
+                    Err(err) => DayOutcome::Failed(err.to_string()),
+                }
+            }
+        },
+    };
+    DayTiming {
+        year: puzzle.year(),
+        day: puzzle.day(),
+        outcome,
+        history_record,
+    }
+}
+
+struct BudgetReport {
+    chart: String,
+    over_budget: bool,
+}
+
+fn format_budget_report(
+    day_timings: &[DayTiming],
+    budget_params: &BudgetParams,
+    highlight: bool,
+) -> BudgetReport {
+    let mut timed_days = day_timings
+        .iter()
+        .filter_map(|day_timing| match day_timing.outcome {
+            DayOutcome::Timed(duration) => Some((day_timing, duration)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    timed_days.sort_by_key(|(day_timing, duration)| {
+        (
+            std::cmp::Reverse(*duration),
+            day_timing.year,
+            day_timing.day,
+        )
+    });
+    let total = timed_days
+        .iter()
+        .map(|(_, duration)| *duration)
+        .sum::<Duration>();
+    let longest = timed_days
+        .first()
+        .map_or(Duration::ZERO, |(_, duration)| *duration);
+    let style = |line: String, over: bool| {
+        if highlight && over {
+            format!("{OVER_BUDGET_STYLE}{line}{RESET_STYLE}")
+        } else {
+            line
+        }
+    };
+
+    let mut lines = vec![];
+    let mut over_budget = false;
+    for (day_timing, duration) in &timed_days {
+        let day_over = budget_params
+            .day_budget
+            .is_some_and(|day_budget| *duration > day_budget);
+        over_budget |= day_over;
+        let bar_length = (duration.as_secs_f64() / longest.as_secs_f64().max(f64::EPSILON)
+            * BAR_WIDTH as f64)
+            .round() as usize;
+        let line = format!(
+            "{} {:<5} {:>10} {:>5.1}%  {}{}",
+            day_timing.year,
+            day_timing.day,
+            format!("{duration:.2?}"),
+            duration.as_secs_f64() / total.as_secs_f64().max(f64::EPSILON) * 100.0,
+            "█".repeat(bar_length.max(1)),
+            if day_over { "  OVER" } else { "" }
+        );
+        lines.push(style(line, day_over));
+    }
+
+    for day_timing in day_timings {
+        match &day_timing.outcome {
+            DayOutcome::Failed(message) => {
+                over_budget = true;
+                lines.push(style(
+                    format!(
+                        "{} {:<5} failed: {message}",
+                        day_timing.year, day_timing.day
+                    ),
+                    true,
+                ));
+            }
+            DayOutcome::NoInput => lines.push(format!(
+                "{} {:<5} skipped, no input",
+                day_timing.year, day_timing.day
+            )),
+            DayOutcome::Timed(_) => {}
+        }
+    }
+
+    let total_over = total > budget_params.total_budget;
+    over_budget |= total_over;
+    lines.push(String::new());
+    lines.push(style(
+        format!(
+            "Total {total:.2?} of {:.2?} budget across {} days",
+            budget_params.total_budget,
+            timed_days.len()
+        ),
+        total_over,
+    ));
+    if let Some(day_budget) = budget_params.day_budget {
+        lines.push(format!("Per-day budget {day_budget:.2?}"));
+    }
+
+    BudgetReport {
+        chart: lines.join("\n"),
+        over_budget,
+    }
+}
+
+pub fn run_budget(budget_params: &BudgetParams) -> Result<(), Box<dyn Error>> {
+    if cfg!(debug_assertions) {
+        return Err("Budgets are measured on release builds, run with cargo run --release".into());
+    }
+    let inputs_directory = match &budget_params.profile {
+        Some(profile) => load_profile(profile)?.inputs,
+        None => budget_params.inputs_directory.clone(),
+    };
+    let day_timings = api::puzzles()
+        .iter()
+        .map(|puzzle| time_day(puzzle, &inputs_directory))
+        .collect::<Vec<_>>();
+    if day_timings
+        .iter()
+        .all(|day_timing| day_timing.outcome == DayOutcome::NoInput)
+    {
+        return Err(format!(
+            "No inputs found, expected files such as {inputs_directory}/2023/day9.txt"
+        )
+        .into());
+    }
+    let history_records = day_timings
+        .iter()
+        .filter_map(|day_timing| day_timing.history_record.clone())
+        .collect::<Vec<_>>();
+    history::record_runs_or_warn(&history_records);
+
+    let budget_report =
+        format_budget_report(&day_timings, budget_params, io::stdout().is_terminal());
+    println!("{}", budget_report.chart);
+    if budget_report.over_budget {
+        return Err("Runtime budget exceeded".into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timed(day: &'static str, micros: u64) -> DayTiming {
+        DayTiming {
+            year: "2023",
+            day,
+            outcome: DayOutcome::Timed(Duration::from_micros(micros)),
+            history_record: None,
+        }
+    }
+
+    #[test]
+    fn chart_sorts_days_by_time() {
+        let expected = "\
+2023 day3      3.00ms  75.0%  ████████████████████████████████████████
+2023 day9      1.00ms  25.0%  █████████████
+2023 day1  skipped, no input
+
+Total 4.00ms of 1.00s budget across 2 days";
+
+        let day_timings = vec![
+            DayTiming {
+                year: "2023",
+                day: "day1",
+                outcome: DayOutcome::NoInput,
+                history_record: None,
+            },
+            timed("day3", 3000),
+            timed("day9", 1000),
+        ];
+        let budget_params = BudgetParams::build(std::iter::empty()).unwrap();
+        let actual = format_budget_report(&day_timings, &budget_params, false);
+
+        assert_eq!(expected, actual.chart);
+        assert!(!actual.over_budget);
+    }
+
+    #[test]
+    fn exceeding_either_budget_fails() {
+        let day_timings = vec![timed("day3", 3000), timed("day9", 1000)];
+
+        let per_day =
+            BudgetParams::build(["--per-day", "2"].into_iter().map(String::from)).unwrap();
+        let actual = format_budget_report(&day_timings, &per_day, false);
+        assert!(actual.over_budget);
+        assert!(actual.chart.lines().next().unwrap().ends_with("  OVER"));
+
+        let total = BudgetParams::build(["--total", "3.5"].into_iter().map(String::from)).unwrap();
+        assert!(format_budget_report(&day_timings, &total, false).over_budget);
+    }
+
+    #[test]
+    fn budgets_must_be_positive() {
+        assert!(BudgetParams::build(["--total", "0"].into_iter().map(String::from)).is_err());
+        assert!(BudgetParams::build(["--per-day", "soon"].into_iter().map(String::from)).is_err());
+        for milliseconds in ["-5", "NaN", "inf", "1e400"] {
+            assert!(
+                BudgetParams::build(["--total", milliseconds].into_iter().map(String::from))
+                    .is_err()
+            );
+        }
+    }
+}
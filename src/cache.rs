@@ -0,0 +1,200 @@
+use std::{collections::HashMap, error::Error, fs, io::ErrorKind};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::Strategy;
+use crate::history::hash_text;
+
+const CACHE_PATH: &str = ".advent_cache.json";
+/// A hash of the source, see `build.rs`.
+const BUILD_ID: &str = env!("ADVENT_BUILD_ID");
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct CacheKey {
+    year: String,
+    day: String,
+    part: u8,
+    /// Both strategies give the same answers, but they are cached apart so each solving
+    /// path still runs once per input and build.
+    strategy: String,
+    build_id: String,
+    input_hash: String,
+}
+
+/// `answer` is `None` for the missing part 2 of days with only one puzzle.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    #[serde(flatten)]
+    key: CacheKey,
+    answer: Option<String>,
+}
+
+/// Windows line endings and the final newline do not change answers, so they do not
+/// change the cache key either. Other whitespace can, as 2022 day5 reads its stacks by
+/// column.
+fn normalize_input(puzzle_input: &str) -> String {
+    let puzzle_input = puzzle_input.replace("\r\n", "\n");
+    match puzzle_input.strip_suffix('\n') {
+        Some(without_final_newline) => without_final_newline.to_string(),
+        None => puzzle_input,
+    }
+}
+
+/// Everything identifying a day's cached answers apart from the part.
+pub struct DayKey {
+    year: String,
+    day: String,
+    strategy: Strategy,
+    input_hash: String,
+}
+
+impl DayKey {
+    pub fn new(year: &str, day: &str, strategy: Strategy, puzzle_input: &str) -> DayKey {
+        DayKey {
+            year: year.to_string(),
+            day: day.to_string(),
+            strategy,
+            input_hash: hash_text(&normalize_input(puzzle_input)),
+        }
+    }
+
+    fn for_part(&self, part: u8) -> CacheKey {
+        CacheKey {
+            year: self.year.clone(),
+            day: self.day.clone(),
+            part,
+            strategy: String::from(self.strategy.name()),
+            build_id: String::from(BUILD_ID),
+            input_hash: self.input_hash.clone(),
+        }
+    }
+}
+
+/// Answers by input, kept in `.advent_cache.json`. Only answers from the running build
+/// are loaded, and saving drops those of earlier builds.
+#[derive(Debug, Default)]
+pub struct AnswerCache {
+    answers: HashMap<CacheKey, Option<String>>,
+    changed: bool,
+}
+
+impl AnswerCache {
+    /// Opens the cache, starting empty when it is missing or unreadable.
+    pub fn open() -> AnswerCache {
+        let entries = fs::read_to_string(CACHE_PATH)
+            .ok()
+            .and_then(|cache| serde_json::from_str::<Vec<CacheEntry>>(&cache).ok())
+            .unwrap_or_default();
+        AnswerCache {
+            answers: entries
+                .into_iter()
+                .filter(|entry| entry.key.build_id == BUILD_ID)
+                .map(|entry| (entry.key, entry.answer))
+                .collect(),
+            changed: false,
+        }
+    }
+
+    /// `None` when the part is not cached, and `Some(None)` when it is cached as missing.
+    pub fn get(&self, day_key: &DayKey, part: u8) -> Option<Option<&str>> {
+        self.answers
+            .get(&day_key.for_part(part))
+            .map(Option::as_deref)
+    }
+
+    pub fn insert(&mut self, day_key: &DayKey, part: u8, answer: Option<&str>) {
+        self.answers
+            .insert(day_key.for_part(part), answer.map(String::from));
+        self.changed = true;
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        if !self.changed {
+            return Ok(());
+        }
+        let entries = self
+            .answers
+            .iter()
+            .map(|(key, answer)| CacheEntry {
+                key: key.clone(),
+                answer: answer.clone(),
+            })
+            .collect::<Vec<_>>();
+        fs::write(CACHE_PATH, serde_json::to_string(&entries)?)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CacheAction {
+    Clear,
+}
+
+pub struct CacheParams {
+    pub action: CacheAction,
+}
+
+impl CacheParams {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<CacheParams, &'static str> {
+        let action = match args.next().as_deref() {
+            Some("clear") => CacheAction::Clear,
+            Some(_) => return Err("Cache action must be clear!"),
+            None => return Err("Cache action not provided!"),
+        };
+        if args.next().is_some() {
+            return Err("Unrecognised flag provided!");
+        }
+        Ok(CacheParams { action })
+    }
+}
+
+pub fn run_cache_command(cache_params: &CacheParams) -> Result<(), Box<dyn Error>> {
+    match cache_params.action {
+        CacheAction::Clear => match fs::remove_file(CACHE_PATH) {
+            Ok(()) => println!("Cleared the answer cache"),
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                println!("The answer cache is already empty")
+            }
+            Err(err) => return Err(err.into()),
+        },
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizing_ignores_line_endings_and_the_final_newline() {
+        let day_key = |puzzle_input| DayKey::new("2022", "day5", Strategy::Combined, puzzle_input);
+
+        assert_eq!(
+            day_key("[A]    \n 1   2 ").input_hash,
+            day_key("[A]    \r\n 1   2 \r\n").input_hash
+        );
+        assert_ne!(
+            day_key("[A]    \n 1   2 ").input_hash,
+            day_key("[A]\n 1   2").input_hash
+        );
+    }
+
+    #[test]
+    fn answers_are_cached_per_part_strategy_and_input() {
+        let day_key = DayKey::new("2023", "day9", Strategy::Combined, "0 3 6");
+        let other_input = DayKey::new("2023", "day9", Strategy::Combined, "1 3 6");
+        let other_day = DayKey::new("2023", "day8", Strategy::Combined, "0 3 6");
+        let separate = DayKey::new("2023", "day9", Strategy::Separate, "0 3 6");
+        let mut answer_cache = AnswerCache::default();
+
+        answer_cache.insert(&day_key, 1, Some("9"));
+        answer_cache.insert(&other_input, 2, None);
+
+        assert_eq!(Some(Some("9")), answer_cache.get(&day_key, 1));
+        assert_eq!(None, answer_cache.get(&day_key, 2));
+        assert_eq!(Some(None), answer_cache.get(&other_input, 2));
+        assert_eq!(None, answer_cache.get(&other_input, 1));
+        assert_eq!(None, answer_cache.get(&other_day, 1));
+        assert_eq!(None, answer_cache.get(&separate, 1));
+    }
+}
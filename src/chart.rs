@@ -0,0 +1,25 @@
+const SPARKLINE_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One block per value, as high as the value is against `maximum`.
+pub fn sparkline(values: &[u64], maximum: u64) -> String {
+    values
+        .iter()
+        .map(|value| {
+            let level = (value * (SPARKLINE_LEVELS.len() as u64 - 1))
+                .checked_div(maximum)
+                .unwrap_or(0);
+            SPARKLINE_LEVELS[level as usize]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_scales_to_maximum() {
+        assert_eq!("▁▄█", sparkline(&[0, 4, 8], 8));
+        assert_eq!("▁▁", sparkline(&[0, 0], 0));
+    }
+}
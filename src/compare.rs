@@ -0,0 +1,291 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs,
+    io::Write,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::api::{self, Answers};
+use crate::inputs::{is_encrypted_path, read_puzzle_input};
+
+const DEFAULT_CONFIG_PATH: &str = "reference_solvers.json";
+const INPUT_PATH_PLACEHOLDER: &str = "{input}";
+
+/// Reference solver commands keyed by year and then day, e.g.
+/// `{"2023": {"day9": ["python3", "py/day9.py", "{input}"]}}`.
+type ReferenceSolvers = HashMap<String, HashMap<String, Vec<String>>>;
+
+pub struct CompareParams {
+    pub year_of_puzzle: String,
+    pub day_to_run: String,
+    pub input_path: String,
+    pub config_path: String,
+}
+
+impl CompareParams {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<CompareParams, &'static str> {
+        let year_of_puzzle = args.next().ok_or("Year of puzzle not provided!")?;
+        let day_to_run = args.next().ok_or("Day to run not provided!")?;
+        let input_path = args.next().ok_or("Path to puzzle input not provided!")?;
+
+        let mut config_path = String::from(DEFAULT_CONFIG_PATH);
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "--config" => {
+                    config_path = args
+                        .next()
+                        .ok_or("Path to reference solver config not provided after --config!")?;
+                }
+                _ => return Err("Unrecognised flag provided!"),
+            }
+        }
+
+        Ok(CompareParams {
+            year_of_puzzle,
+            day_to_run,
+            input_path,
+            config_path,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct PartComparison {
+    part: u8,
+    ours: String,
+    reference: Option<String>,
+}
+
+impl PartComparison {
+    fn matches(&self) -> bool {
+        self.reference.as_deref() == Some(self.ours.as_str())
+    }
+}
+
+fn find_reference_command<'a>(
+    reference_solvers: &'a ReferenceSolvers,
+    year_of_puzzle: &str,
+    day_to_run: &str,
+) -> Result<&'a [String], Box<dyn Error>> {
+    reference_solvers
+        .get(year_of_puzzle)
+        .and_then(|days| days.get(day_to_run))
+        .filter(|command| !command.is_empty())
+        .map(Vec::as_slice)
+        .ok_or_else(|| {
+            format!("No reference solver configured for {year_of_puzzle} {day_to_run}").into()
+        })
+}
+
+/// The reference solver's arguments with `{input}` replaced by the input path. Encrypted
+/// inputs are refused there, since the solver would read ciphertext, and they are only
+/// decrypted in memory.
+fn reference_arguments(
+    reference_command: &[String],
+    input_path: &str,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let takes_path = reference_command[1..]
+        .iter()
+        .any(|argument| argument.contains(INPUT_PATH_PLACEHOLDER));
+    if takes_path && is_encrypted_path(input_path) {
+        return Err(format!(
+            "{input_path} is encrypted, so the reference solver must read it from stdin instead of {INPUT_PATH_PLACEHOLDER}"
+        )
+        .into());
+    }
+    Ok(reference_command[1..]
+        .iter()
+        .map(|argument| argument.replace(INPUT_PATH_PLACEHOLDER, input_path))
+        .collect())
+}
+
+/// Runs the reference solver with the input on stdin and any `{input}` argument
+/// replaced by the input path, returning its stdout and how long it took.
+fn run_reference_solver(
+    reference_command: &[String],
+    input_path: &str,
+    puzzle_input: &str,
+) -> Result<(String, Duration), Box<dyn Error>> {
+    let arguments = reference_arguments(reference_command, input_path)?;
+
+    let start = Instant::now();
+    let mut child = Command::new(&reference_command[0])
+        .args(arguments)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|err| format!("Failed to start {}: {err}", reference_command[0]))?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or("Failed to open reference solver stdin")?;
+    let puzzle_input = puzzle_input.to_string();
+    let writer = thread::spawn(move || {
+        // Solvers that read the input path instead of stdin may exit without reading.
+        let _ = stdin.write_all(puzzle_input.as_bytes());
+    });
+    let output = child.wait_with_output()?;
+    let elapsed = start.elapsed();
+    let _ = writer.join();
+
+    if !output.status.success() {
+        return Err(format!("Reference solver exited with {}", output.status).into());
+    }
+    Ok((String::from_utf8(output.stdout)?, elapsed))
+}
+
+/// Pairs our answers with the reference output, which should print one answer per line.
+/// Single-part days compare only the first line.
+fn compare_answers(answers: &Answers, reference_output: &str) -> Vec<PartComparison> {
+    let mut reference_lines = reference_output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    [(1, Some(&answers.first)), (2, answers.second.as_ref())]
+        .into_iter()
+        .filter_map(|(part, answer)| Some((part, answer?)))
+        .map(|(part, answer)| PartComparison {
+            part,
+            ours: answer.to_line(),
+            reference: reference_lines.next().map(str::to_string),
+        })
+        .collect()
+}
+
+pub fn compare_with_reference(compare_params: &CompareParams) -> Result<(), Box<dyn Error>> {
+    let config = fs::read_to_string(&compare_params.config_path).map_err(|err| {
+        format!(
+            "Failed to read reference solver config {}: {err}",
+            compare_params.config_path
+        )
+    })?;
+    let reference_solvers: ReferenceSolvers = serde_json::from_str(&config)?;
+    let reference_command = find_reference_command(
+        &reference_solvers,
+        &compare_params.year_of_puzzle,
+        &compare_params.day_to_run,
+    )?;
+
+    let puzzle = api::puzzle(&compare_params.year_of_puzzle, &compare_params.day_to_run)?;
+    let puzzle_input = read_puzzle_input(&compare_params.input_path)?;
+
+    let start = Instant::now();
+    let answers = puzzle.solve(&puzzle_input)?;
+    let our_time = start.elapsed();
+
+    let (reference_output, reference_time) =
+        run_reference_solver(reference_command, &compare_params.input_path, &puzzle_input)?;
+
+    let comparisons = compare_answers(&answers, &reference_output);
+    for comparison in &comparisons {
+        match &comparison.reference {
+            _ if comparison.matches() => {
+                println!("Part {}: match ({})", comparison.part, comparison.ours)
+            }
+            Some(reference) => println!(
+                "Part {}: MISMATCH ours={} reference={reference}",
+                comparison.part, comparison.ours
+            ),
+            None => println!(
+                "Part {}: MISMATCH ours={} reference printed no answer",
+                comparison.part, comparison.ours
+            ),
+        }
+    }
+    println!(
+        "Time: ours {our_time:?}, reference {reference_time:?} ({:.1}x)",
+        reference_time.as_secs_f64() / our_time.as_secs_f64().max(f64::EPSILON)
+    );
+
+    if comparisons.iter().all(PartComparison::matches) {
+        Ok(())
+    } else {
+        Err("Answers differ from the reference solver".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::Answer;
+
+    #[test]
+    fn compare_answers_line_by_line() {
+        let expected = vec![
+            PartComparison {
+                part: 1,
+                ours: String::from("114"),
+                reference: Some(String::from("114")),
+            },
+            PartComparison {
+                part: 2,
+                ours: String::from("EHZ"),
+                reference: None,
+            },
+        ];
+
+        let answers = Answers {
+            first: Answer::Integer(114),
+            second: Some(Answer::Letters {
+                decoded: String::from("EHZ"),
+                picture: String::from("####"),
+            }),
+        };
+        let actual = compare_answers(&answers, "\n114\n");
+
+        assert_eq!(expected, actual);
+        assert!(actual[0].matches());
+        assert!(!actual[1].matches());
+    }
+
+    #[test]
+    fn single_part_days_compare_one_line() {
+        let answers = Answers {
+            first: Answer::Text(String::from("2=-1=0")),
+            second: None,
+        };
+
+        let actual = compare_answers(&answers, "2=-1=0\n");
+
+        assert_eq!(1, actual.len());
+        assert!(actual[0].matches());
+    }
+
+    #[test]
+    fn missing_reference_solver_is_an_error() {
+        let reference_solvers: ReferenceSolvers =
+            serde_json::from_str(r#"{"2023": {"day9": ["python3", "day9.py"]}}"#).unwrap();
+
+        assert!(find_reference_command(&reference_solvers, "2023", "day9").is_ok());
+        assert!(find_reference_command(&reference_solvers, "2023", "day8").is_err());
+    }
+
+    #[test]
+    fn encrypted_inputs_are_not_passed_by_path() {
+        let by_path = ["python3", "day9.py", "{input}"].map(String::from);
+        let by_stdin = ["python3", "day9.py"].map(String::from);
+
+        assert!(reference_arguments(&by_path, "day9.txt.enc").is_err());
+        assert!(reference_arguments(&by_stdin, "day9.txt.enc").is_ok());
+        assert_eq!(
+            vec!["day9.py", "day9.txt"],
+            reference_arguments(&by_path, "day9.txt").unwrap()[..]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn reference_solver_reads_input_from_stdin_and_path() -> Result<(), Box<dyn Error>> {
+        let reference_command = ["sh", "-c", "cat; echo \"$0\"", "{input}"].map(String::from);
+
+        let (actual, _) = run_reference_solver(&reference_command, "input.txt", "42\n")?;
+
+        assert_eq!("42\ninput.txt\n", actual);
+        Ok(())
+    }
+}
@@ -0,0 +1,412 @@
+use std::error::Error;
+
+use crate::api::{self, Puzzle};
+use crate::inputs::read_puzzle_input;
+use crate::{run_advent_day, CLIParams};
+
+pub struct DetectParams {
+    pub input_path: String,
+    pub run_best_match: bool,
+}
+
+impl DetectParams {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<DetectParams, &'static str> {
+        let input_path = args.next().ok_or("Path to puzzle input not provided!")?;
+
+        let mut run_best_match = false;
+        for flag in args {
+            match flag.as_str() {
+                "--run" => run_best_match = true,
+                _ => return Err("Unrecognised flag provided!"),
+            }
+        }
+
+        Ok(DetectParams {
+            input_path,
+            run_best_match,
+        })
+    }
+}
+
+/// A structural feature of one day's inputs that other days' inputs do not share.
+struct Heuristic {
+    year: &'static str,
+    day: &'static str,
+    description: &'static str,
+    matches: fn(&str) -> bool,
+}
+
+fn non_empty_lines(puzzle_input: &str) -> impl Iterator<Item = &str> {
+    puzzle_input.lines().filter(|line| !line.trim().is_empty())
+}
+
+fn is_number(word: &str) -> bool {
+    !word.is_empty() && word.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Lines such as `Game 12: ...` or `Card   3: ...`, which pad the number with spaces.
+fn has_numbered_prefix(line: &str, label: &str) -> bool {
+    line.strip_prefix(label)
+        .and_then(|rest| rest.split_once(':'))
+        .is_some_and(|(number, _)| is_number(number.trim_start()))
+}
+
+/// Whether there are lines and every one that is not blank matches.
+fn all_lines(puzzle_input: &str, matches: impl Fn(&str) -> bool) -> bool {
+    let mut lines = non_empty_lines(puzzle_input).peekable();
+    lines.peek().is_some() && lines.all(matches)
+}
+
+fn all_lines_numbered(puzzle_input: &str, label: &str) -> bool {
+    all_lines(puzzle_input, |line| has_numbered_prefix(line, label))
+}
+
+fn has_calorie_groups(puzzle_input: &str) -> bool {
+    puzzle_input.contains("\n\n") && all_lines(puzzle_input, |line| is_number(line.trim()))
+}
+
+fn has_strategy_guide(puzzle_input: &str) -> bool {
+    all_lines(puzzle_input, |line| {
+        matches!(line.trim_end().as_bytes(), [b'A'..=b'C', b' ', b'X'..=b'Z'])
+    })
+}
+
+fn has_rucksacks(puzzle_input: &str) -> bool {
+    all_lines(puzzle_input, |line| {
+        line.len() % 2 == 0 && line.bytes().all(|item| item.is_ascii_alphabetic())
+    })
+}
+
+fn has_section_pairs(puzzle_input: &str) -> bool {
+    all_lines(puzzle_input, |line| {
+        line.split(',').count() == 2
+            && line.split(',').all(|range| {
+                range
+                    .split_once('-')
+                    .is_some_and(|(start, end)| is_number(start) && is_number(end))
+            })
+    })
+}
+
+fn has_calibration_lines(puzzle_input: &str) -> bool {
+    puzzle_input.bytes().any(|byte| byte.is_ascii_lowercase())
+        && all_lines(puzzle_input, |line| {
+            line.bytes()
+                .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit())
+        })
+}
+
+/// A rectangle of digits and symbols on a background of `.`.
+fn has_schematic_grid(puzzle_input: &str) -> bool {
+    let width = non_empty_lines(puzzle_input).next().map_or(0, str::len);
+    width > 1
+        && puzzle_input.contains('.')
+        && puzzle_input.bytes().any(|byte| byte.is_ascii_digit())
+        && all_lines(puzzle_input, |line| {
+            line.len() == width && !line.contains(char::is_whitespace)
+        })
+}
+
+fn has_hands_and_bids(puzzle_input: &str) -> bool {
+    all_lines(puzzle_input, |line| {
+        line.split_once(' ').is_some_and(|(hand, bid)| {
+            hand.len() == 5
+                && hand.chars().all(|card| "23456789TJQKA".contains(card))
+                && is_number(bid.trim())
+        })
+    })
+}
+
+fn has_number_sequences(puzzle_input: &str) -> bool {
+    all_lines(puzzle_input, |line| {
+        let numbers = line.split_whitespace().collect::<Vec<_>>();
+        numbers.len() > 1
+            && numbers
+                .iter()
+                .all(|number| is_number(number.strip_prefix('-').unwrap_or(number)))
+    })
+}
+
+/// `move 1 from 2 to 1` lines after a drawing of the stacks.
+fn has_crate_moves(puzzle_input: &str) -> bool {
+    let Some((_, moves)) = puzzle_input.split_once("\n\n") else {
+        return false;
+    };
+    let mut moves = non_empty_lines(moves).peekable();
+    moves.peek().is_some()
+        && moves.all(|line| {
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            matches!(
+                words.as_slice(),
+                ["move", count, "from", source, "to", target]
+                    if is_number(count) && is_number(source) && is_number(target)
+            )
+        })
+}
+
+fn has_seeds_header(puzzle_input: &str) -> bool {
+    puzzle_input.trim_start().starts_with("seeds:")
+}
+
+fn has_race_records(puzzle_input: &str) -> bool {
+    let mut lines = non_empty_lines(puzzle_input);
+    lines.next().is_some_and(|line| line.starts_with("Time:"))
+        && lines
+            .next()
+            .is_some_and(|line| line.starts_with("Distance:"))
+}
+
+/// A line of `L` and `R` turns followed by `AAA = (BBB, CCC)` nodes.
+fn has_network_nodes(puzzle_input: &str) -> bool {
+    let mut lines = non_empty_lines(puzzle_input);
+    let has_turns = lines
+        .next()
+        .is_some_and(|turns| turns.bytes().all(|turn| turn == b'L' || turn == b'R'));
+    let mut nodes = lines.peekable();
+    has_turns
+        && nodes.peek().is_some()
+        && nodes.all(|line| {
+            line.split_once(" = (")
+                .is_some_and(|(node, targets)| !node.is_empty() && targets.ends_with(')'))
+        })
+}
+
+fn is_snafu(puzzle_input: &str) -> bool {
+    puzzle_input.contains(['-', '='])
+        && all_lines(puzzle_input, |line| {
+            line.bytes().all(|digit| b"210-=".contains(&digit))
+        })
+}
+
+const HEURISTICS: [Heuristic; 15] = [
+    Heuristic {
+        year: "2022",
+        day: "day1",
+        description: "numbers in blank-line groups",
+        matches: has_calorie_groups,
+    },
+    Heuristic {
+        year: "2022",
+        day: "day2",
+        description: "A X strategy lines",
+        matches: has_strategy_guide,
+    },
+    Heuristic {
+        year: "2022",
+        day: "day3",
+        description: "rucksack item lines",
+        matches: has_rucksacks,
+    },
+    Heuristic {
+        year: "2022",
+        day: "day4",
+        description: "a-b,c-d section pairs",
+        matches: has_section_pairs,
+    },
+    Heuristic {
+        year: "2022",
+        day: "day5",
+        description: "move N from A to B lines",
+        matches: has_crate_moves,
+    },
+    Heuristic {
+        year: "2022",
+        day: "day25",
+        description: "SNAFU numbers",
+        matches: is_snafu,
+    },
+    Heuristic {
+        year: "2023",
+        day: "day1",
+        description: "calibration lines",
+        matches: has_calibration_lines,
+    },
+    Heuristic {
+        year: "2023",
+        day: "day2",
+        description: "Game N: prefixes",
+        matches: |puzzle_input| all_lines_numbered(puzzle_input, "Game "),
+    },
+    Heuristic {
+        year: "2023",
+        day: "day3",
+        description: "an engine schematic grid",
+        matches: has_schematic_grid,
+    },
+    Heuristic {
+        year: "2023",
+        day: "day4",
+        description: "Card N: prefixes",
+        matches: |puzzle_input| all_lines_numbered(puzzle_input, "Card "),
+    },
+    Heuristic {
+        year: "2023",
+        day: "day5",
+        description: "a seeds: header",
+        matches: has_seeds_header,
+    },
+    Heuristic {
+        year: "2023",
+        day: "day6",
+        description: "Time: and Distance: lines",
+        matches: has_race_records,
+    },
+    Heuristic {
+        year: "2023",
+        day: "day7",
+        description: "hands and bids",
+        matches: has_hands_and_bids,
+    },
+    Heuristic {
+        year: "2023",
+        day: "day8",
+        description: "turns and network nodes",
+        matches: has_network_nodes,
+    },
+    Heuristic {
+        year: "2023",
+        day: "day9",
+        description: "number sequences",
+        matches: has_number_sequences,
+    },
+];
+
+#[derive(Debug, PartialEq)]
+struct Candidate {
+    year: &'static str,
+    day: &'static str,
+    parses: bool,
+    features: Vec<&'static str>,
+}
+
+impl Candidate {
+    /// A matching structure counts for more than parsing, which many parsers do leniently.
+    fn score(&self) -> usize {
+        self.features.len() * 2 + usize::from(self.parses)
+    }
+
+    fn describe(&self) -> String {
+        let mut evidence = vec![];
+        if self.parses {
+            evidence.push(String::from("parses"));
+        } else {
+            evidence.push(String::from("does not parse"));
+        }
+        evidence.extend(self.features.iter().map(|feature| format!("has {feature}")));
+        evidence.join(", ")
+    }
+}
+
+/// Parses the input with every registered puzzle, keeping those that parse or whose
+/// inputs look alike, best first.
+fn rank_candidates(puzzles: &[Puzzle], puzzle_input: &str) -> Vec<Candidate> {
+    let mut candidates = puzzles
+        .iter()
+        .map(|puzzle| Candidate {
+            year: puzzle.year(),
+            day: puzzle.day(),
+            parses: puzzle.parse(puzzle_input).is_ok(),
+            features: HEURISTICS
+                .iter()
+                .filter(|heuristic| {
+                    heuristic.year == puzzle.year()
+                        && heuristic.day == puzzle.day()
+                        && (heuristic.matches)(puzzle_input)
+                })
+                .map(|heuristic| heuristic.description)
+                .collect(),
+        })
+        .filter(|candidate| candidate.score() > 0)
+        .collect::<Vec<_>>();
+
+    // Stable, so equal scores keep the registry's year and day order.
+    candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.score()));
+    candidates
+}
+
+fn format_candidates(candidates: &[Candidate]) -> String {
+    candidates
+        .iter()
+        .enumerate()
+        .map(|(rank, candidate)| {
+            format!(
+                "{:>2}. {} {:<5}  {}",
+                rank + 1,
+                candidate.year,
+                candidate.day,
+                candidate.describe()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn detect_puzzle(detect_params: &DetectParams) -> Result<(), Box<dyn Error>> {
+    let puzzle_input = read_puzzle_input(&detect_params.input_path)?;
+    let candidates = rank_candidates(&api::puzzles(), &puzzle_input);
+    let best_match = candidates
+        .first()
+        .ok_or("No registered puzzle parses this input or looks like it")?;
+    println!("{}", format_candidates(&candidates));
+
+    if detect_params.run_best_match {
+        println!();
+        println!("Running {} {}", best_match.year, best_match.day);
+        let cli_params = CLIParams::build(
+            [
+                "rust-advent",
+                best_match.year,
+                best_match.day,
+                &detect_params.input_path,
+            ]
+            .into_iter()
+            .map(String::from),
+        )?;
+        run_advent_day(&cli_params)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crate_moves_need_every_move_line() {
+        let input = "    [D]\n[N] [C]\n 1   2\n\nmove 1 from 2 to 1\nmove 3 from 1 to 2\n";
+
+        assert!(has_crate_moves(input));
+        assert!(!has_crate_moves(&input.replace("move 3", "take 3")));
+        assert!(!has_crate_moves("move 1 from 2 to 1"));
+    }
+
+    #[test]
+    fn numbered_prefixes_allow_padding() {
+        assert!(all_lines_numbered(
+            "Card   1: 41 48 | 83 86\nCard  12: 13 32 | 61 30",
+            "Card "
+        ));
+        assert!(!all_lines_numbered("Game x: 3 blue", "Game "));
+        assert!(!all_lines_numbered("", "Game "));
+    }
+
+    #[test]
+    #[cfg(feature = "y2023")]
+    fn structure_outranks_parsing() {
+        let input = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue";
+
+        let actual = rank_candidates(&api::puzzles(), input);
+
+        assert_eq!(
+            Some(&Candidate {
+                year: "2023",
+                day: "day2",
+                parses: true,
+                features: vec!["Game N: prefixes"],
+            }),
+            actual.first()
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::{error::Error, fs};
+
+use crate::find_puzzle;
+use crate::inputs::read_puzzle_input;
+
+pub struct ParseParams {
+    pub year_of_puzzle: String,
+    pub day_to_run: String,
+    pub input_path: String,
+    pub output_path: Option<String>,
+}
+
+impl ParseParams {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<ParseParams, &'static str> {
+        let year_of_puzzle = args.next().ok_or("Year of puzzle not provided!")?;
+        let day_to_run = args.next().ok_or("Day to run not provided!")?;
+        let input_path = args.next().ok_or("Path to puzzle input not provided!")?;
+
+        let mut output_path = None;
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "--output" => {
+                    output_path = Some(
+                        args.next()
+                            .ok_or("Path to JSON output not provided after --output!")?,
+                    );
+                }
+                _ => return Err("Unrecognised flag provided!"),
+            }
+        }
+
+        Ok(ParseParams {
+            year_of_puzzle,
+            day_to_run,
+            input_path,
+            output_path,
+        })
+    }
+}
+
+fn parsed_input_to_json(
+    year_of_puzzle: &str,
+    day_to_run: &str,
+    puzzle_input: &str,
+) -> Result<String, Box<dyn Error>> {
+    let puzzle_to_parse = find_puzzle(year_of_puzzle, day_to_run)?;
+    let advent_day = puzzle_to_parse.parse_input(puzzle_input)?;
+    let mut json = advent_day.to_json_pretty()?;
+    json.push('\n');
+    Ok(json)
+}
+
+pub fn dump_parsed_input(parse_params: &ParseParams) -> Result<(), Box<dyn Error>> {
+    let puzzle_input = read_puzzle_input(&parse_params.input_path)?;
+    let json = parsed_input_to_json(
+        &parse_params.year_of_puzzle,
+        &parse_params.day_to_run,
+        &puzzle_input,
+    )?;
+    match &parse_params.output_path {
+        Some(output_path) => fs::write(output_path, json)?,
+        None => print!("{json}"),
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "y2023"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dump_2023_day_2_games() -> Result<(), Box<dyn Error>> {
+        let expected = serde_json::json!({
+            "parsed_input": [{
+                "identifier": 1,
+                "revealed_cubes": [
+                    { "red": 4, "blue": 3, "green": 0 },
+                    { "red": 1, "blue": 6, "green": 2 },
+                ],
+            }],
+        });
+
+        let json = parsed_input_to_json(
+            "2023",
+            "day2",
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue",
+        )?;
+        let actual: serde_json::Value = serde_json::from_str(&json)?;
+
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+
+    #[test]
+    fn dump_2023_day_8_node_map() -> Result<(), Box<dyn Error>> {
+        let expected = serde_json::json!({
+            "moves": ["Left", "Right"],
+            "node_map": { "AAA": ["BBB", "ZZZ"] },
+        });
+
+        let json = parsed_input_to_json("2023", "day8", "LR\n\nAAA = (BBB, ZZZ)")?;
+        let actual: serde_json::Value = serde_json::from_str(&json)?;
+
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+}
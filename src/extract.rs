@@ -0,0 +1,455 @@
+use std::{
+    error::Error,
+    fs,
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+use crate::inputs::plaintext_path;
+use crate::show::{decode_entities, puzzle_page_path, tokenize_html, HtmlToken};
+
+const EXAMPLES_DIRECTORY: &str = "examples";
+const PREVIEW_LINES: usize = 3;
+
+pub struct ExtractParams {
+    pub year_of_puzzle: String,
+    pub day_to_run: String,
+    pub page_path: Option<String>,
+    pub assume_defaults: bool,
+    pub overwrite: bool,
+}
+
+impl ExtractParams {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<ExtractParams, &'static str> {
+        let year_of_puzzle = args.next().ok_or("Year of puzzle not provided!")?;
+        let day_to_run = args.next().ok_or("Day to run not provided!")?;
+
+        let mut page_path = None;
+        let mut assume_defaults = false;
+        let mut overwrite = false;
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "--page" => {
+                    page_path = Some(
+                        args.next()
+                            .ok_or("Path to puzzle page not provided after --page!")?,
+                    );
+                }
+                "--yes" => assume_defaults = true,
+                "--force" => overwrite = true,
+                _ => return Err("Unrecognised flag provided!"),
+            }
+        }
+
+        Ok(ExtractParams {
+            year_of_puzzle,
+            day_to_run,
+            page_path,
+            assume_defaults,
+            overwrite,
+        })
+    }
+}
+
+/// Where the example input for a day is kept, e.g. `examples/2023/day9.txt`.
+pub fn example_input_path(year_of_puzzle: &str, day_to_run: &str) -> String {
+    format!("{EXAMPLES_DIRECTORY}/{year_of_puzzle}/{day_to_run}.txt")
+}
+
+/// The answers file next to an input, holding one answer per line in part order.
+/// Encrypted inputs share the answers file of their plaintext.
+pub fn answers_path(input_path: &str) -> String {
+    Path::new(plaintext_path(input_path))
+        .with_extension("answers")
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// The answers in an answers file, or `None` when it cannot be read.
+pub fn read_answers(answers_path: &str) -> Option<Vec<String>> {
+    let answers = fs::read_to_string(answers_path).ok()?;
+    Some(
+        answers
+            .lines()
+            .map(|line| line.trim().to_string())
+            .collect(),
+    )
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct PageExamples {
+    inputs: Vec<String>,
+    first_answers: Vec<String>,
+    second_answers: Vec<String>,
+}
+
+/// Collects the `<pre><code>` blocks and the `<code><em>` values of each part's article.
+fn find_examples(page: &str) -> PageExamples {
+    let mut page_examples = PageExamples::default();
+    let mut article_count = 0;
+    let mut in_article = false;
+    let mut preformatted = false;
+    let mut code_depth = 0;
+    let mut emphasis_depth = 0;
+    let mut text = String::new();
+    for token in tokenize_html(page) {
+        match token {
+            HtmlToken::Open(tag) => match tag.as_str() {
+                "article" => {
+                    in_article = true;
+                    article_count += 1;
+                }
+                "pre" => {
+                    preformatted = true;
+                    text.clear();
+                }
+                "code" => {
+                    code_depth += 1;
+                    if !preformatted && emphasis_depth == 0 {
+                        text.clear();
+                    }
+                }
+                "em" => {
+                    emphasis_depth += 1;
+                    if !preformatted && code_depth == 0 {
+                        text.clear();
+                    }
+                }
+                _ => {}
+            },
+            HtmlToken::Close(tag) => match tag.as_str() {
+                "article" => in_article = false,
+                "pre" => {
+                    preformatted = false;
+                    // Solvers expect inputs without the trailing newline the block ends with.
+                    let example_input = std::mem::take(&mut text);
+                    if in_article {
+                        page_examples
+                            .inputs
+                            .push(example_input.trim_end_matches('\n').to_string());
+                    }
+                }
+                "code" | "em" => {
+                    let is_answer = in_article
+                        && !preformatted
+                        && code_depth > 0
+                        && emphasis_depth > 0
+                        && !text.trim().is_empty();
+                    if is_answer {
+                        let answer = std::mem::take(&mut text).trim().to_string();
+                        match article_count {
+                            1 => page_examples.first_answers.push(answer),
+                            _ => page_examples.second_answers.push(answer),
+                        }
+                    }
+                    if tag == "code" {
+                        code_depth -= 1;
+                    } else {
+                        emphasis_depth -= 1;
+                    }
+                }
+                _ => {}
+            },
+            HtmlToken::Text(fragment) => {
+                if preformatted || (code_depth > 0 && emphasis_depth > 0) {
+                    text.push_str(&decode_entities(fragment));
+                }
+            }
+        }
+    }
+    page_examples
+}
+
+fn preview(candidate: &str) -> String {
+    let lines = candidate.lines().collect::<Vec<_>>();
+    let mut preview = lines
+        .iter()
+        .take(PREVIEW_LINES)
+        .map(|line| format!("      {line}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if lines.len() > PREVIEW_LINES {
+        preview.push_str(&format!("\n      ... ({} lines)", lines.len()));
+    }
+    preview
+}
+
+/// Picks one of several candidates, asking on `input` unless there is nothing to
+/// choose between. The default is used when the reply is empty.
+fn choose_candidate(
+    label: &str,
+    candidates: &[String],
+    default_index: usize,
+    assume_defaults: bool,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Option<String>, Box<dyn Error>> {
+    if candidates.len() <= 1 || assume_defaults {
+        return Ok(candidates.get(default_index).cloned());
+    }
+
+    writeln!(output, "Several candidates found for the {label}:")?;
+    for (index, candidate) in candidates.iter().enumerate() {
+        writeln!(output, "  [{}]\n{}", index + 1, preview(candidate))?;
+    }
+    loop {
+        write!(
+            output,
+            "Which one should be saved? [1-{}, enter for {}, s to skip] ",
+            candidates.len(),
+            default_index + 1
+        )?;
+        output.flush()?;
+        let mut reply = String::new();
+        if input.read_line(&mut reply)? == 0 {
+            return Err("No choice made before the end of input".into());
+        }
+        match reply.trim() {
+            "" => return Ok(candidates.get(default_index).cloned()),
+            "s" => return Ok(None),
+            reply => match reply.parse::<usize>() {
+                Ok(choice) if (1..=candidates.len()).contains(&choice) => {
+                    return Ok(Some(candidates[choice - 1].clone()))
+                }
+                _ => writeln!(
+                    output,
+                    "Please enter a number between 1 and {}",
+                    candidates.len()
+                )?,
+            },
+        }
+    }
+}
+
+/// The answers to save, one per part. A part without a chosen answer keeps the stored
+/// one, so re-extracting a page saved before part 2 unlocked does not lose it. Answers
+/// stop at the first part with neither, as the file is read by line.
+fn merge_answers(chosen: Vec<Option<String>>, stored: Vec<String>) -> Vec<String> {
+    let mut stored = stored.into_iter();
+    chosen
+        .into_iter()
+        .map(|answer| {
+            let stored_answer = stored.next().filter(|answer| !answer.is_empty());
+            answer.or(stored_answer)
+        })
+        .map_while(|answer| answer)
+        .collect()
+}
+
+/// Asks before replacing files that already exist. With `--yes` there is nobody to
+/// ask, so existing files are only replaced with `--force`.
+fn confirm_overwrite(
+    existing_paths: &[&str],
+    assume_defaults: bool,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<bool, Box<dyn Error>> {
+    if existing_paths.is_empty() {
+        return Ok(true);
+    }
+    let (existing_paths, exist) = match existing_paths {
+        [path] => (path.to_string(), "exists"),
+        paths => (paths.join(" and "), "exist"),
+    };
+    if assume_defaults {
+        return Err(
+            format!("{existing_paths} already {exist}, run with --force to overwrite").into(),
+        );
+    }
+    write!(
+        output,
+        "{existing_paths} already {exist}. Overwrite? [y/N] "
+    )?;
+    output.flush()?;
+    let mut reply = String::new();
+    input.read_line(&mut reply)?;
+    Ok(matches!(reply.trim(), "y" | "Y" | "yes"))
+}
+
+pub fn extract_examples(extract_params: &ExtractParams) -> Result<(), Box<dyn Error>> {
+    let page_path = extract_params.page_path.clone().unwrap_or_else(|| {
+        puzzle_page_path(&extract_params.year_of_puzzle, &extract_params.day_to_run)
+    });
+    let page = fs::read_to_string(&page_path)
+        .map_err(|err| format!("Failed to read saved puzzle page {page_path}: {err}"))?;
+    let page_examples = find_examples(&page);
+
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut output = io::stdout();
+    let assume_defaults = extract_params.assume_defaults;
+
+    // The first block is almost always the example, and a part's final highlighted
+    // value is almost always its answer.
+    let example_input = choose_candidate(
+        "example input",
+        &page_examples.inputs,
+        0,
+        assume_defaults,
+        &mut input,
+        &mut output,
+    )?
+    .ok_or("No example input chosen or found in the page")?;
+    let mut chosen_answers = vec![];
+    for (part, candidates) in [
+        (1, &page_examples.first_answers),
+        (2, &page_examples.second_answers),
+    ] {
+        let answer = choose_candidate(
+            &format!("part {part} answer"),
+            candidates,
+            candidates.len().saturating_sub(1),
+            assume_defaults,
+            &mut input,
+            &mut output,
+        )?;
+        chosen_answers.push(answer);
+    }
+
+    let example_path =
+        example_input_path(&extract_params.year_of_puzzle, &extract_params.day_to_run);
+    let answers_path = answers_path(&example_path);
+    let answers = merge_answers(
+        chosen_answers,
+        read_answers(&answers_path).unwrap_or_default(),
+    );
+    if !extract_params.overwrite {
+        let existing_paths = [example_path.as_str(), answers_path.as_str()]
+            .into_iter()
+            .filter(|path| Path::new(path).exists())
+            .collect::<Vec<_>>();
+        if !confirm_overwrite(&existing_paths, assume_defaults, &mut input, &mut output)? {
+            println!("Kept the existing example");
+            return Ok(());
+        }
+    }
+
+    if let Some(example_directory) = Path::new(&example_path).parent() {
+        fs::create_dir_all(example_directory)?;
+    }
+    fs::write(&example_path, example_input)?;
+    if answers.is_empty() {
+        println!("Saved the example input to {example_path}, no answers were found");
+        return Ok(());
+    }
+    fs::write(&answers_path, answers.join("\n") + "\n")?;
+    println!(
+        "Saved the example input to {example_path} and {} answer(s) to {answers_path}",
+        answers.len()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "<main><article class=\"day-desc\"><h2>--- Day 9 ---</h2>\
+<p>For example:</p><pre><code>0 3 6\n1 3 &lt;6\n</code></pre>\
+<p>Next is <code><em>18</em></code>, then <code><em>28</em></code>. Total: <code><em>114</em></code>.</p>\
+</article><p>Your answer: <code>1</code></p>\
+<article class=\"day-desc\"><p>Now <em>backwards</em>: <code><em>2</em></code>.</p></article></main>";
+
+    #[test]
+    fn find_example_blocks_and_answers() {
+        let expected = PageExamples {
+            inputs: vec![String::from("0 3 6\n1 3 <6")],
+            first_answers: vec![String::from("18"), String::from("28"), String::from("114")],
+            second_answers: vec![String::from("2")],
+        };
+
+        let actual = find_examples(PAGE);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn blocks_outside_articles_do_not_hide_answers() {
+        let expected = PageExamples {
+            inputs: vec![String::from("1 2")],
+            first_answers: vec![String::from("3")],
+            second_answers: vec![],
+        };
+
+        let page = "<pre>header</pre><article><pre><code>1 2\n</code></pre>\
+<p>Sum: <code><em>3</em></code></p></article>";
+        let actual = find_examples(page);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn choose_candidate_reads_choice_and_default() -> Result<(), Box<dyn Error>> {
+        let candidates = vec![String::from("18"), String::from("114")];
+
+        let mut output = vec![];
+        let chosen = choose_candidate(
+            "answer",
+            &candidates,
+            1,
+            false,
+            &mut "7\n1\n".as_bytes(),
+            &mut output,
+        )?;
+        assert_eq!(Some(String::from("18")), chosen);
+        assert!(String::from_utf8(output)?.contains("Please enter a number between 1 and 2"));
+
+        let chosen = choose_candidate(
+            "answer",
+            &candidates,
+            1,
+            false,
+            &mut "\n".as_bytes(),
+            &mut vec![],
+        )?;
+        assert_eq!(Some(String::from("114")), chosen);
+        Ok(())
+    }
+
+    #[test]
+    fn stored_answers_are_kept_when_the_page_lacks_them() {
+        let chosen = vec![Some(String::from("114")), None];
+
+        assert_eq!(
+            vec![String::from("114"), String::from("2")],
+            merge_answers(chosen.clone(), vec![String::from("18"), String::from("2")])
+        );
+        assert_eq!(vec![String::from("114")], merge_answers(chosen, vec![]));
+        assert!(merge_answers(vec![None, Some(String::from("2"))], vec![]).is_empty());
+    }
+
+    #[test]
+    fn existing_examples_are_only_replaced_when_confirmed() -> Result<(), Box<dyn Error>> {
+        let existing = ["examples/2023/day9.txt"];
+
+        assert!(confirm_overwrite(
+            &[],
+            true,
+            &mut "".as_bytes(),
+            &mut vec![]
+        )?);
+        assert!(confirm_overwrite(
+            &existing,
+            false,
+            &mut "y\n".as_bytes(),
+            &mut vec![]
+        )?);
+        assert!(!confirm_overwrite(
+            &existing,
+            false,
+            &mut "\n".as_bytes(),
+            &mut vec![]
+        )?);
+        assert!(confirm_overwrite(&existing, true, &mut "".as_bytes(), &mut vec![]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn answers_file_sits_next_to_the_input() {
+        assert_eq!(
+            "examples/2023/day9.answers",
+            answers_path(&example_input_path("2023", "day9"))
+        );
+        assert_eq!("inputs/alice.answers", answers_path("inputs/alice.txt.enc"));
+    }
+}
@@ -0,0 +1,245 @@
+//! C API over the solvers, built with the `ffi` feature and exported from the crate's
+//! `cdylib`, e.g. by `cargo build --features ffi`. Its header is checked in at
+//! `include/rust_advent.h`.
+//!
+//! Every string returned by the library is owned by it and must be released with
+//! `advent_free_string` or `advent_free_answers`.
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    ptr, slice,
+};
+
+use crate::api::{self, AdventError, Answer};
+
+/// Outcome of a call into the library. Anything other than `ADVENT_STATUS_OK` comes with
+/// a message in `AdventAnswers::error`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdventStatus {
+    Ok = 0,
+    InvalidArgument = 1,
+    YearNotImplemented = 2,
+    YearNotBuilt = 3,
+    DayNotImplemented = 4,
+    InvalidInput = 5,
+    SolverFailed = 6,
+}
+
+impl From<&AdventError> for AdventStatus {
+    fn from(err: &AdventError) -> AdventStatus {
+        match err {
+            AdventError::YearNotImplemented { .. } => AdventStatus::YearNotImplemented,
+            AdventError::YearNotBuilt { .. } => AdventStatus::YearNotBuilt,
+            AdventError::DayNotImplemented { .. } => AdventStatus::DayNotImplemented,
+            AdventError::InvalidInput { .. } => AdventStatus::InvalidInput,
+            AdventError::SolverFailed { .. } | AdventError::PartNotAvailable { .. } => {
+                AdventStatus::SolverFailed
+            }
+        }
+    }
+}
+
+/// Answers to a puzzle, single-line with picture answers decoded to letters. `second` is
+/// null for days with only one part, and both are null when `error` is set.
+#[repr(C)]
+#[derive(Debug)]
+pub struct AdventAnswers {
+    pub first: *mut c_char,
+    pub second: *mut c_char,
+    pub error: *mut c_char,
+}
+
+/// Converts to a C string, dropping any interior NUL bytes, which no answer contains.
+fn into_c_string(text: String) -> *mut c_char {
+    let text = text.replace('\0', "");
+    CString::new(text).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// # Safety
+/// `text` must be null or a valid NUL-terminated string.
+unsafe fn read_c_str<'a>(text: *const c_char) -> Option<&'a str> {
+    if text.is_null() {
+        return None;
+    }
+    CStr::from_ptr(text).to_str().ok()
+}
+
+fn answer_to_c_string(answer: &Answer) -> *mut c_char {
+    into_c_string(answer.to_line())
+}
+
+/// Lists the registered puzzles, one `<year> <day>` per line, ordered by year and day.
+/// Release the result with `advent_free_string`.
+#[no_mangle]
+pub extern "C" fn advent_list_puzzles() -> *mut c_char {
+    let puzzles = api::puzzles()
+        .iter()
+        .map(|puzzle| format!("{} {}", puzzle.year(), puzzle.day()))
+        .collect::<Vec<_>>();
+    into_c_string(puzzles.join("\n"))
+}
+
+/// Parses and solves `input_length` bytes of `input` with the solver registered for
+/// `year` (e.g. `"2023"`) and `day` (e.g. `"day9"`), filling in `answers`. The input does
+/// not need to be NUL-terminated but must be UTF-8. Release the answers with
+/// `advent_free_answers`, whatever the status.
+///
+/// # Safety
+/// `year` and `day` must be null or NUL-terminated strings, `input` must be null or point
+/// to at least `input_length` readable bytes and `answers` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn advent_solve(
+    year: *const c_char,
+    day: *const c_char,
+    input: *const u8,
+    input_length: usize,
+    answers: *mut AdventAnswers,
+) -> AdventStatus {
+    if answers.is_null() {
+        return AdventStatus::InvalidArgument;
+    }
+    let (status, solved) = solve(year, day, input, input_length);
+    let solved = match solved {
+        Ok((first, second)) => AdventAnswers {
+            first,
+            second,
+            error: ptr::null_mut(),
+        },
+        Err(message) => AdventAnswers {
+            first: ptr::null_mut(),
+            second: ptr::null_mut(),
+            error: into_c_string(message),
+        },
+    };
+    answers.write(solved);
+    status
+}
+
+type SolvedStrings = Result<(*mut c_char, *mut c_char), String>;
+
+/// # Safety
+/// As for `advent_solve`.
+unsafe fn solve(
+    year: *const c_char,
+    day: *const c_char,
+    input: *const u8,
+    input_length: usize,
+) -> (AdventStatus, SolvedStrings) {
+    let (Some(year), Some(day)) = (read_c_str(year), read_c_str(day)) else {
+        return (
+            AdventStatus::InvalidArgument,
+            Err(String::from("Year and day must be UTF-8 strings")),
+        );
+    };
+    let puzzle_input = if input.is_null() {
+        (input_length == 0).then_some("")
+    } else {
+        std::str::from_utf8(slice::from_raw_parts(input, input_length)).ok()
+    };
+    let Some(puzzle_input) = puzzle_input else {
+        return (
+            AdventStatus::InvalidArgument,
+            Err(String::from("Puzzle input must be a UTF-8 buffer")),
+        );
+    };
+
+    match api::puzzle(year, day).and_then(|puzzle| puzzle.solve(puzzle_input)) {
+        Ok(answers) => (
+            AdventStatus::Ok,
+            Ok((
+                answer_to_c_string(&answers.first),
+                answers
+                    .second
+                    .as_ref()
+                    .map_or(ptr::null_mut(), answer_to_c_string),
+            )),
+        ),
+        Err(err) => (AdventStatus::from(&err), Err(err.to_string())),
+    }
+}
+
+/// Releases a string returned by the library. Null is ignored.
+///
+/// # Safety
+/// `text` must be null or a string returned by the library that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn advent_free_string(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
+
+/// Releases the strings held by `answers` and sets them to null, so freeing twice is safe.
+///
+/// # Safety
+/// `answers` must be null or filled in by `advent_solve`.
+#[no_mangle]
+pub unsafe extern "C" fn advent_free_answers(answers: *mut AdventAnswers) {
+    let Some(answers) = answers.as_mut() else {
+        return;
+    };
+    for text in [&mut answers.first, &mut answers.second, &mut answers.error] {
+        advent_free_string(*text);
+        *text = ptr::null_mut();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unsafe fn solve_str(year: &CStr, day: &CStr, input: &str) -> (AdventStatus, AdventAnswers) {
+        let mut answers = AdventAnswers {
+            first: ptr::null_mut(),
+            second: ptr::null_mut(),
+            error: ptr::null_mut(),
+        };
+        let status = advent_solve(
+            year.as_ptr(),
+            day.as_ptr(),
+            input.as_ptr(),
+            input.len(),
+            &mut answers,
+        );
+        (status, answers)
+    }
+
+    unsafe fn owned(text: *mut c_char) -> Option<String> {
+        read_c_str(text).map(String::from)
+    }
+
+    #[test]
+    #[cfg(feature = "y2023")]
+    fn solve_fills_in_both_answers() {
+        unsafe {
+            let (status, mut answers) = solve_str(
+                c"2023",
+                c"day9",
+                "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45",
+            );
+
+            assert_eq!(AdventStatus::Ok, status);
+            assert_eq!(Some(String::from("114")), owned(answers.first));
+            assert_eq!(Some(String::from("2")), owned(answers.second));
+            assert!(answers.error.is_null());
+            advent_free_answers(&mut answers);
+            assert!(answers.first.is_null());
+        }
+    }
+
+    #[test]
+    fn errors_carry_a_status_and_message() {
+        unsafe {
+            let (status, mut answers) = solve_str(c"2015", c"day1", "");
+
+            assert_eq!(AdventStatus::YearNotImplemented, status);
+            assert_eq!(
+                Some(String::from("Year 2015 has not been implemented yet")),
+                owned(answers.error)
+            );
+            assert!(answers.first.is_null());
+            advent_free_answers(&mut answers);
+        }
+    }
+}
@@ -0,0 +1,446 @@
+use std::{
+    error::Error,
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::chart::sparkline;
+
+const HISTORY_PATH: &str = ".advent_history.jsonl";
+const DEFAULT_THRESHOLD_PERCENT: f64 = 20.0;
+const DEFAULT_BASELINE_WINDOW: usize = 5;
+const TREND_LENGTH: usize = 20;
+/// The part recorded for days solved in one combined pass, which are timed as a whole.
+pub const BOTH_PARTS: u8 = 0;
+
+/// What timed a run. Each keeps its own baseline, as they time different work: budget
+/// runs include parsing, and batch runs solve each part on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunSource {
+    Single,
+    Batch,
+    Budget,
+    Verify,
+}
+
+impl RunSource {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RunSource::Single => "run",
+            RunSource::Batch => "batch",
+            RunSource::Budget => "budget",
+            RunSource::Verify => "verify",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    pub timestamp: u64,
+    pub year: String,
+    pub day: String,
+    pub part: u8,
+    pub duration_us: u64,
+    pub git_commit: String,
+    pub crate_version: String,
+    /// Debug and release timings differ too much to share a baseline. Empty for runs
+    /// recorded before the profile was.
+    #[serde(default)]
+    pub build_profile: String,
+    /// Empty for runs recorded before the source was, which were all single runs.
+    #[serde(default)]
+    pub source: String,
+    pub answer_hash: String,
+}
+
+impl HistoryRecord {
+    pub fn new(
+        year: &str,
+        day: &str,
+        part: u8,
+        duration: Duration,
+        answer: &str,
+        source: RunSource,
+    ) -> HistoryRecord {
+        HistoryRecord {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since_epoch| since_epoch.as_secs()),
+            year: year.to_string(),
+            day: day.to_string(),
+            part,
+            duration_us: duration.as_micros() as u64,
+            git_commit: String::from(env!("ADVENT_GIT_COMMIT")),
+            crate_version: String::from(env!("CARGO_PKG_VERSION")),
+            build_profile: String::from(build_profile()),
+            source: String::from(source.name()),
+            answer_hash: hash_text(answer),
+        }
+    }
+}
+
+fn build_profile() -> &'static str {
+    match cfg!(debug_assertions) {
+        true => "debug",
+        false => "release",
+    }
+}
+
+/// FNV-1a, so hashes stay comparable between builds and Rust versions.
+pub fn hash_text(text: &str) -> String {
+    let hash = text.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+/// Appends records to the history store, which is only ever added to.
+fn record_runs(history_records: &[HistoryRecord]) -> Result<(), Box<dyn Error>> {
+    let mut history_file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_PATH)?;
+    let mut lines = String::new();
+    for history_record in history_records {
+        lines.push_str(&serde_json::to_string(history_record)?);
+        lines.push('\n');
+    }
+    history_file.write_all(lines.as_bytes())?;
+    Ok(())
+}
+
+/// Records runs, only warning on failure as the run itself succeeded.
+pub fn record_runs_or_warn(history_records: &[HistoryRecord]) {
+    if history_records.is_empty() {
+        return;
+    }
+    if let Err(err) = record_runs(history_records) {
+        tracing::warn!("Failed to record run history: {err}");
+    }
+}
+
+fn read_history() -> Result<Vec<HistoryRecord>, Box<dyn Error>> {
+    let history = match fs::read_to_string(HISTORY_PATH) {
+        Ok(history) => history,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err.into()),
+    };
+    Ok(parse_history(&history))
+}
+
+/// Skips lines that are not records, so one bad line does not hide the rest.
+fn parse_history(history: &str) -> Vec<HistoryRecord> {
+    history
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(line_index, line)| match serde_json::from_str(line) {
+            Ok(history_record) => Some(history_record),
+            Err(err) => {
+                tracing::warn!("Skipping line {} of {HISTORY_PATH}: {err}", line_index + 1);
+                None
+            }
+        })
+        .collect()
+}
+
+pub struct HistoryParams {
+    pub year_of_puzzle: Option<String>,
+    pub day_to_run: Option<String>,
+    pub threshold_percent: f64,
+    pub baseline_window: usize,
+}
+
+impl HistoryParams {
+    pub fn build(args: impl Iterator<Item = String>) -> Result<HistoryParams, &'static str> {
+        let mut args = args.peekable();
+        let year_of_puzzle = args.next_if(|arg| !arg.starts_with("--"));
+        let day_to_run = args.next_if(|arg| !arg.starts_with("--"));
+
+        let mut threshold_percent = DEFAULT_THRESHOLD_PERCENT;
+        let mut baseline_window = DEFAULT_BASELINE_WINDOW;
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "--threshold" => {
+                    threshold_percent = args
+                        .next()
+                        .ok_or("Threshold percentage not provided after --threshold!")?
+                        .parse()
+                        .map_err(|_| "Threshold must be a percentage!")?;
+                }
+                "--window" => {
+                    baseline_window = args
+                        .next()
+                        .ok_or("Number of baseline runs not provided after --window!")?
+                        .parse()
+                        .ok()
+                        .filter(|window| *window > 0)
+                        .ok_or("Baseline window must be a positive number of runs!")?;
+                }
+                _ => return Err("Unrecognised flag provided!"),
+            }
+        }
+
+        Ok(HistoryParams {
+            year_of_puzzle,
+            day_to_run,
+            threshold_percent,
+            baseline_window,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Regression {
+    run_index: usize,
+    baseline_us: u64,
+}
+
+fn median(durations: &mut [u64]) -> u64 {
+    durations.sort_unstable();
+    durations[durations.len() / 2]
+}
+
+/// Flags runs slower than the median of the `baseline_window` runs before them by
+/// more than `threshold_percent`.
+fn find_regressions(
+    durations: &[u64],
+    baseline_window: usize,
+    threshold_percent: f64,
+) -> Vec<Regression> {
+    (baseline_window..durations.len())
+        .filter_map(|run_index| {
+            let baseline_us =
+                median(&mut durations[run_index - baseline_window..run_index].to_vec());
+            let limit = baseline_us as f64 * (1.0 + threshold_percent / 100.0);
+            (durations[run_index] as f64 > limit).then_some(Regression {
+                run_index,
+                baseline_us,
+            })
+        })
+        .collect()
+}
+
+fn source_name(history_record: &HistoryRecord) -> &str {
+    match history_record.source.as_str() {
+        "" => RunSource::Single.name(),
+        source => source,
+    }
+}
+
+/// Single runs are labelled by part and build alone, as they make up most of the history.
+fn part_label(part: u8, build_profile: &str, source: &str) -> String {
+    let part_label = match part {
+        BOTH_PARTS => String::from("both parts"),
+        part => format!("part {part}"),
+    };
+    let details = [build_profile, source]
+        .into_iter()
+        .filter(|detail| !detail.is_empty() && *detail != RunSource::Single.name())
+        .collect::<Vec<_>>();
+    match details.is_empty() {
+        true => part_label,
+        false => format!("{part_label} ({})", details.join(", ")),
+    }
+}
+
+fn format_history(history_records: &[HistoryRecord], history_params: &HistoryParams) -> String {
+    let mut keys = history_records
+        .iter()
+        .filter(|record| {
+            history_params
+                .year_of_puzzle
+                .as_ref()
+                .is_none_or(|year| *year == record.year)
+                && history_params
+                    .day_to_run
+                    .as_ref()
+                    .is_none_or(|day| *day == record.day)
+        })
+        .map(|record| {
+            (
+                record.year.as_str(),
+                record.day.as_str(),
+                record.part,
+                record.build_profile.as_str(),
+                source_name(record),
+            )
+        })
+        .collect::<Vec<_>>();
+    keys.sort_by_key(|(year, day, part, build_profile, source)| {
+        (*year, day.len(), *day, *part, *build_profile, *source)
+    });
+    keys.dedup();
+
+    let mut report = vec![];
+    for (year, day, part, build_profile, source) in keys {
+        let runs = history_records
+            .iter()
+            .filter(|record| {
+                record.year == year
+                    && record.day == day
+                    && record.part == part
+                    && record.build_profile == build_profile
+                    && source_name(record) == source
+            })
+            .collect::<Vec<_>>();
+        let durations = runs.iter().map(|run| run.duration_us).collect::<Vec<_>>();
+        let trend = &durations[durations.len().saturating_sub(TREND_LENGTH)..];
+        let latest = runs[runs.len() - 1];
+        report.push(format!(
+            "{year} {day} {}: {} runs, latest {:?} at {}  {}",
+            part_label(part, build_profile, source),
+            runs.len(),
+            Duration::from_micros(latest.duration_us),
+            latest.git_commit,
+            sparkline(trend, trend.iter().copied().max().unwrap_or(0))
+        ));
+        for regression in find_regressions(
+            &durations,
+            history_params.baseline_window,
+            history_params.threshold_percent,
+        ) {
+            let run = runs[regression.run_index];
+            report.push(format!(
+                "  SLOWER run {} at {} (v{}): {:?} against a baseline of {:?} (+{:.0}%)",
+                regression.run_index + 1,
+                run.git_commit,
+                run.crate_version,
+                Duration::from_micros(run.duration_us),
+                Duration::from_micros(regression.baseline_us),
+                (run.duration_us as f64 / regression.baseline_us.max(1) as f64 - 1.0) * 100.0
+            ));
+        }
+    }
+    if report.is_empty() {
+        report.push(String::from("No runs recorded yet"));
+    }
+    report.join("\n")
+}
+
+pub fn show_history(history_params: &HistoryParams) -> Result<(), Box<dyn Error>> {
+    println!("{}", format_history(&read_history()?, history_params));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(part: u8, duration_us: u64) -> HistoryRecord {
+        HistoryRecord {
+            timestamp: 0,
+            year: String::from("2023"),
+            day: String::from("day9"),
+            part,
+            duration_us,
+            git_commit: String::from("abc1234"),
+            crate_version: String::from("0.2.1"),
+            build_profile: String::from("release"),
+            source: String::from("run"),
+            answer_hash: hash_text("114"),
+        }
+    }
+
+    #[test]
+    fn regressions_compare_against_rolling_median() {
+        let expected = vec![Regression {
+            run_index: 3,
+            baseline_us: 100,
+        }];
+
+        let actual = find_regressions(&[100, 90, 110, 150, 115], 3, 20.0);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn history_flags_slower_runs_per_part() {
+        let history_records = [100, 100, 100, 100, 100, 200]
+            .into_iter()
+            .map(|duration_us| record(1, duration_us))
+            .chain([record(2, 50)])
+            .collect::<Vec<_>>();
+        let history_params = HistoryParams::build(std::iter::empty()).unwrap();
+
+        let actual = format_history(&history_records, &history_params);
+
+        assert!(actual.contains("2023 day9 part 1 (release): 6 runs, latest 200µs at abc1234"));
+        assert!(actual.contains(
+            "  SLOWER run 6 at abc1234 (v0.2.1): 200µs against a baseline of 100µs (+100%)"
+        ));
+        assert!(actual.contains("2023 day9 part 2 (release): 1 runs"));
+    }
+
+    #[test]
+    fn combined_runs_are_labelled_both_parts() {
+        let history_params = HistoryParams::build(std::iter::empty()).unwrap();
+
+        let actual = format_history(&[record(BOTH_PARTS, 80)], &history_params);
+
+        assert!(actual.starts_with("2023 day9 both parts (release): 1 runs, latest 80µs"));
+    }
+
+    #[test]
+    fn build_profiles_have_separate_baselines() {
+        let history_records = [100, 100, 100, 100, 100]
+            .into_iter()
+            .map(|duration_us| record(1, duration_us))
+            .chain([HistoryRecord {
+                build_profile: String::from("debug"),
+                ..record(1, 2000)
+            }])
+            .collect::<Vec<_>>();
+        let history_params = HistoryParams::build(std::iter::empty()).unwrap();
+
+        let actual = format_history(&history_records, &history_params);
+
+        assert!(actual.contains("2023 day9 part 1 (debug): 1 runs"));
+        assert!(!actual.contains("SLOWER"));
+    }
+
+    #[test]
+    fn sources_have_separate_baselines() {
+        let history_records = [100, 100, 100, 100, 100]
+            .into_iter()
+            .map(|duration_us| record(BOTH_PARTS, duration_us))
+            .chain([HistoryRecord {
+                source: String::from("budget"),
+                ..record(BOTH_PARTS, 400)
+            }])
+            .chain([HistoryRecord {
+                source: String::new(),
+                ..record(BOTH_PARTS, 100)
+            }])
+            .collect::<Vec<_>>();
+        let history_params = HistoryParams::build(std::iter::empty()).unwrap();
+
+        let actual = format_history(&history_records, &history_params);
+
+        assert!(actual.contains("2023 day9 both parts (release): 6 runs"));
+        assert!(actual.contains("2023 day9 both parts (release, budget): 1 runs"));
+        assert!(!actual.contains("SLOWER"));
+    }
+
+    #[test]
+    fn malformed_history_lines_are_skipped() {
+        let history = format!(
+            "{}\nnot a record\n\n{}\n",
+            serde_json::to_string(&record(1, 100)).unwrap(),
+            serde_json::to_string(&record(2, 50)).unwrap()
+        );
+
+        let actual = parse_history(&history);
+
+        assert_eq!(vec![record(1, 100), record(2, 50)], actual);
+    }
+
+    #[test]
+    fn answer_hash_is_stable() {
+        assert_eq!("cbf29ce484222325", hash_text(""));
+        assert_ne!(hash_text("114"), hash_text("2"));
+    }
+}
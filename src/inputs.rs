@@ -0,0 +1,304 @@
+use std::{
+    env,
+    error::Error,
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
+    sync::OnceLock,
+};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+const DEFAULT_KEY_PATH: &str = ".advent_key";
+const KEY_PATH_VARIABLE: &str = "ADVENT_KEY_FILE";
+const ENCRYPTED_EXTENSION: &str = "enc";
+const ENCRYPTED_MAGIC: &[u8] = b"AOCENC1";
+const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+
+/// The key file given with `--key` before the command, see `set_key_path`.
+static KEY_PATH: OnceLock<String> = OnceLock::new();
+
+#[derive(Debug, PartialEq)]
+pub enum InputsAction {
+    Keygen,
+    Lock,
+    Unlock,
+}
+
+pub struct InputsParams {
+    pub action: InputsAction,
+    pub input_paths: Vec<String>,
+    pub key_path: String,
+    /// Whether `lock` and `unlock` may replace files that already exist.
+    pub overwrite: bool,
+}
+
+impl InputsParams {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<InputsParams, &'static str> {
+        let action = match args.next().as_deref() {
+            Some("keygen") => InputsAction::Keygen,
+            Some("lock") => InputsAction::Lock,
+            Some("unlock") => InputsAction::Unlock,
+            Some(_) => return Err("Inputs action must be keygen, lock or unlock!"),
+            None => return Err("Inputs action not provided!"),
+        };
+
+        let mut input_paths = vec![];
+        let mut key_path = key_file_path();
+        let mut overwrite = false;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--force" => overwrite = true,
+                "--key" => {
+                    key_path = args
+                        .next()
+                        .ok_or("Path to key file not provided after --key!")?;
+                }
+                flag if flag.starts_with("--") => return Err("Unrecognised flag provided!"),
+                _ => input_paths.push(arg),
+            }
+        }
+        if action != InputsAction::Keygen && input_paths.is_empty() {
+            return Err("Path to puzzle input not provided!");
+        }
+
+        Ok(InputsParams {
+            action,
+            input_paths,
+            key_path,
+            overwrite,
+        })
+    }
+}
+
+/// Sets the key file given with `--key` before the command. Every command then reads
+/// encrypted inputs with that key file, so an input locked with
+/// `inputs lock --key <path>` can be run with the same flag.
+pub fn set_key_path(key_path: String) {
+    // Only the first one counts, as the flag is taken once per run.
+    let _ = KEY_PATH.set(key_path);
+}
+
+/// The key file given with `--key` before the command, or the one named by
+/// `ADVENT_KEY_FILE`, or `.advent_key` in the working directory.
+fn key_file_path() -> String {
+    if let Some(key_path) = KEY_PATH.get() {
+        return key_path.clone();
+    }
+    env::var(KEY_PATH_VARIABLE).unwrap_or_else(|_| String::from(DEFAULT_KEY_PATH))
+}
+
+fn encode_key(key: &[u8]) -> String {
+    key.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_key(encoded_key: &str) -> Result<Key, Box<dyn Error>> {
+    let encoded_key = encoded_key.trim();
+    if encoded_key.len() != KEY_LENGTH * 2 || !encoded_key.is_ascii() {
+        return Err("Key file must contain 64 hexadecimal characters".into());
+    }
+    let key = (0..encoded_key.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&encoded_key[index..index + 2], 16))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| "Key file must contain 64 hexadecimal characters")?;
+    Ok(*Key::from_slice(&key))
+}
+
+fn read_key(key_path: &str) -> Result<Key, Box<dyn Error>> {
+    let encoded_key = fs::read_to_string(key_path)
+        .map_err(|err| format!("Failed to read key file {key_path}: {err}"))?;
+    decode_key(&encoded_key)
+}
+
+fn encrypt_input(key: &Key, puzzle_input: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let cipher = ChaCha20Poly1305::new(key);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, puzzle_input)
+        .map_err(|_| "Failed to encrypt puzzle input")?;
+
+    let mut encrypted_input = ENCRYPTED_MAGIC.to_vec();
+    encrypted_input.extend_from_slice(&nonce);
+    encrypted_input.extend_from_slice(&ciphertext);
+    Ok(encrypted_input)
+}
+
+fn decrypt_input(key: &Key, encrypted_input: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let encrypted_input = encrypted_input
+        .strip_prefix(ENCRYPTED_MAGIC)
+        .filter(|rest| rest.len() >= NONCE_LENGTH)
+        .ok_or("Not an encrypted puzzle input")?;
+    let (nonce, ciphertext) = encrypted_input.split_at(NONCE_LENGTH);
+    ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| {
+            "Failed to decrypt puzzle input, the key is wrong or the file was modified".into()
+        })
+}
+
+pub(crate) fn is_encrypted_path(input_path: &str) -> bool {
+    Path::new(input_path)
+        .extension()
+        .is_some_and(|extension| extension == ENCRYPTED_EXTENSION)
+}
+
+/// The path of the plaintext an input was encrypted from, e.g. `day9.txt` for `day9.txt.enc`.
+pub(crate) fn plaintext_path(input_path: &str) -> &str {
+    match is_encrypted_path(input_path) {
+        true => &input_path[..input_path.len() - ENCRYPTED_EXTENSION.len() - 1],
+        false => input_path,
+    }
+}
+
+/// Reads a puzzle input, decrypting `.enc` files in memory with the key file.
+pub fn read_puzzle_input(input_path: &str) -> Result<String, Box<dyn Error>> {
+    let _read_span = tracing::info_span!("read_input", path = input_path).entered();
+    if !is_encrypted_path(input_path) {
+        return Ok(fs::read_to_string(input_path)?);
+    }
+    let key = read_key(&key_file_path())?;
+    let puzzle_input = decrypt_input(&key, &fs::read(input_path)?)?;
+    Ok(String::from_utf8(puzzle_input)?)
+}
+
+/// Where a collection keeps its input for a day, e.g. `inputs/2023/day9.txt`, or its
+/// encrypted `.txt.enc` form when only that exists.
+pub fn collection_input_path(inputs_directory: &str, year: &str, day: &str) -> Option<String> {
+    let input_path = format!("{inputs_directory}/{year}/{day}.txt");
+    [
+        input_path.clone(),
+        format!("{input_path}.{ENCRYPTED_EXTENSION}"),
+    ]
+    .into_iter()
+    .find(|path| Path::new(path).is_file())
+}
+
+/// Writes an output of `inputs lock` or `unlock`, refusing to replace an existing file
+/// unless `overwrite` is set.
+fn write_output(
+    output_path: &Path,
+    contents: &[u8],
+    overwrite: bool,
+) -> Result<(), Box<dyn Error>> {
+    let mut output_file = match OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .create_new(!overwrite)
+        .open(output_path)
+    {
+        Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+            return Err(format!(
+                "{} already exists, run with --force to overwrite it",
+                output_path.display()
+            )
+            .into())
+        }
+        output_file => output_file?,
+    };
+    output_file.write_all(contents)?;
+    Ok(())
+}
+
+fn generate_key(key_path: &str) -> Result<(), Box<dyn Error>> {
+    let mut key_options = OpenOptions::new();
+    key_options.write(true).create_new(true);
+    // Created readable by the owner alone, so the key is never readable by others.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        key_options.mode(0o600);
+    }
+    let mut key_file = match key_options.open(key_path) {
+        Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+            return Err(
+                format!("Key file {key_path} already exists, refusing to overwrite it").into(),
+            )
+        }
+        key_file => key_file?,
+    };
+    let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+    key_file.write_all(format!("{}\n", encode_key(&key)).as_bytes())?;
+    println!("Wrote new key to {key_path}");
+    Ok(())
+}
+
+pub fn run_inputs_command(inputs_params: &InputsParams) -> Result<(), Box<dyn Error>> {
+    if inputs_params.action == InputsAction::Keygen {
+        return generate_key(&inputs_params.key_path);
+    }
+
+    let key = read_key(&inputs_params.key_path)?;
+    for input_path in &inputs_params.input_paths {
+        match inputs_params.action {
+            InputsAction::Lock => {
+                let locked_path = format!("{input_path}.{ENCRYPTED_EXTENSION}");
+                write_output(
+                    Path::new(&locked_path),
+                    &encrypt_input(&key, &fs::read(input_path)?)?,
+                    inputs_params.overwrite,
+                )?;
+                println!("Locked {input_path} into {locked_path}");
+            }
+            _ => {
+                if !is_encrypted_path(input_path) {
+                    return Err(format!("{input_path} does not end in .enc").into());
+                }
+                let unlocked_path = Path::new(input_path).with_extension("");
+                write_output(
+                    &unlocked_path,
+                    &decrypt_input(&key, &fs::read(input_path)?)?,
+                    inputs_params.overwrite,
+                )?;
+                println!("Unlocked {input_path} into {}", unlocked_path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrypt_reverses_encrypt() -> Result<(), Box<dyn Error>> {
+        let expected = b"0 3 6 9 12 15\n".to_vec();
+
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let actual = decrypt_input(&key, &encrypt_input(&key, &expected)?)?;
+
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+
+    #[test]
+    fn decrypt_rejects_wrong_key_and_tampering() -> Result<(), Box<dyn Error>> {
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let other_key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let mut encrypted_input = encrypt_input(&key, b"LR\n\nAAA = (BBB, ZZZ)")?;
+
+        assert!(decrypt_input(&other_key, &encrypted_input).is_err());
+
+        let last = encrypted_input.len() - 1;
+        encrypted_input[last] ^= 1;
+        assert!(decrypt_input(&key, &encrypted_input).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn key_round_trips_through_hex() -> Result<(), Box<dyn Error>> {
+        let expected = ChaCha20Poly1305::generate_key(&mut OsRng);
+
+        let actual = decode_key(&format!("{}\n", encode_key(&expected)))?;
+
+        assert_eq!(expected, actual);
+        assert!(decode_key("not a key").is_err());
+        Ok(())
+    }
+}
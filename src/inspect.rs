@@ -0,0 +1,426 @@
+use std::{collections::HashMap, error::Error};
+
+use serde_json::Value;
+
+use crate::find_puzzle;
+use crate::inputs::read_puzzle_input;
+
+const HISTOGRAM_LENGTH: usize = 12;
+
+pub struct InspectParams {
+    pub input_path: String,
+    pub puzzle_to_parse: Option<(String, String)>,
+}
+
+impl InspectParams {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<InspectParams, &'static str> {
+        let input_path = args.next().ok_or("Path to puzzle input not provided!")?;
+
+        let mut puzzle_to_parse = None;
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "--parse" => {
+                    let year_of_puzzle = args
+                        .next()
+                        .ok_or("Year of puzzle not provided after --parse!")?;
+                    let day_to_run = args
+                        .next()
+                        .ok_or("Day to run not provided after --parse!")?;
+                    puzzle_to_parse = Some((year_of_puzzle, day_to_run));
+                }
+                _ => return Err("Unrecognised flag provided!"),
+            }
+        }
+
+        Ok(InspectParams {
+            input_path,
+            puzzle_to_parse,
+        })
+    }
+}
+
+/// Integers found in the input, with a `-` sign when it does not join two words.
+fn find_numbers(line: &str) -> Vec<&str> {
+    let bytes = line.as_bytes();
+    let mut numbers = vec![];
+    let mut index = 0;
+    while index < bytes.len() {
+        if !bytes[index].is_ascii_digit() {
+            index += 1;
+            continue;
+        }
+        let mut start = index;
+        if start > 0
+            && bytes[start - 1] == b'-'
+            && (start == 1 || !bytes[start - 2].is_ascii_alphanumeric())
+        {
+            start -= 1;
+        }
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+        }
+        numbers.push(&line[start..index]);
+    }
+    numbers
+}
+
+#[derive(Debug, PartialEq)]
+struct NumberSummary {
+    count: usize,
+    /// The smallest and largest numbers, unless every number overflowed.
+    range: Option<(i128, i128)>,
+    most_digits: usize,
+    most_per_line: usize,
+    /// Numbers too large to hold even in an `i128`.
+    overflowing: usize,
+}
+
+impl NumberSummary {
+    /// Whether some number fits neither a `u32` nor, for negative numbers, an `i32`.
+    fn exceeds_u32(&self) -> bool {
+        self.overflowing > 0
+            || self.range.is_some_and(|(minimum, maximum)| {
+                minimum < i32::MIN as i128 || maximum > u32::MAX as i128
+            })
+    }
+}
+
+fn summarize_numbers(lines: &[&str]) -> Option<NumberSummary> {
+    let numbers_per_line = lines
+        .iter()
+        .map(|line| find_numbers(line))
+        .collect::<Vec<_>>();
+    let numbers = numbers_per_line.iter().flatten().collect::<Vec<_>>();
+    let values = numbers
+        .iter()
+        .filter_map(|number| number.parse::<i128>().ok())
+        .collect::<Vec<_>>();
+    Some(NumberSummary {
+        count: numbers.len(),
+        range: values
+            .iter()
+            .min()
+            .zip(values.iter().max())
+            .map(|(minimum, maximum)| (*minimum, *maximum)),
+        most_digits: numbers
+            .iter()
+            .map(|number| number.trim_start_matches('-').len())
+            .max()?,
+        most_per_line: numbers_per_line.iter().map(Vec::len).max()?,
+        overflowing: numbers.len() - values.len(),
+    })
+}
+
+/// Character counts, most frequent first, when every line has the same length.
+fn grid_histogram(lines: &[&str]) -> Option<Vec<(char, usize)>> {
+    let width = lines.first()?.chars().count();
+    if lines.len() < 2 || width < 2 || lines.iter().any(|line| line.chars().count() != width) {
+        return None;
+    }
+    let mut character_counts = HashMap::new();
+    for character in lines.iter().flat_map(|line| line.chars()) {
+        *character_counts.entry(character).or_insert(0) += 1;
+    }
+    let mut histogram = character_counts.into_iter().collect::<Vec<_>>();
+    histogram.sort_by_key(|(character, count)| (std::cmp::Reverse(*count), *character));
+    Some(histogram)
+}
+
+fn profile_input(puzzle_input: &str) -> String {
+    let lines = puzzle_input.lines().collect::<Vec<_>>();
+    let sections = puzzle_input
+        .split("\n\n")
+        .filter(|section| !section.trim().is_empty())
+        .count();
+    let mut report = vec![
+        format!("Lines        {}", lines.len()),
+        format!("Sections     {sections}"),
+        format!(
+            "Final line   {}",
+            if puzzle_input.ends_with('\n') {
+                "ends with a newline"
+            } else {
+                "has no newline"
+            }
+        ),
+    ];
+
+    match grid_histogram(&lines) {
+        Some(histogram) => {
+            report.push(format!(
+                "Grid         {} x {}",
+                lines[0].chars().count(),
+                lines.len()
+            ));
+            let mut characters = histogram
+                .iter()
+                .take(HISTOGRAM_LENGTH)
+                .map(|(character, count)| format!("{character:?} {count}"))
+                .collect::<Vec<_>>();
+            if histogram.len() > HISTOGRAM_LENGTH {
+                characters.push(format!("and {} more", histogram.len() - HISTOGRAM_LENGTH));
+            }
+            report.push(format!("Characters   {}", characters.join(", ")));
+        }
+        None => report.push(String::from("Grid         not rectangular")),
+    }
+
+    match summarize_numbers(&lines) {
+        Some(numbers) => {
+            let range = numbers
+                .range
+                .map(|(minimum, maximum)| format!(" from {minimum} to {maximum}"))
+                .unwrap_or_default();
+            report.push(format!(
+                "Numbers      {}{range}, up to {} digits, up to {} per line",
+                numbers.count, numbers.most_digits, numbers.most_per_line
+            ));
+            if numbers.overflowing > 0 {
+                report.push(format!(
+                    "             {} too large for i128 left out of the range",
+                    numbers.overflowing
+                ));
+            }
+            report.push(format!(
+                "Exceeds u32  {}",
+                if numbers.exceeds_u32() { "yes" } else { "no" }
+            ));
+        }
+        None => report.push(String::from("Numbers      none")),
+    }
+    report.join("\n")
+}
+
+/// A JSON number, kept as an integer when it is one so large values compare exactly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum JsonNumber {
+    Integer(i128),
+    Float(f64),
+}
+
+impl JsonNumber {
+    fn from_number(number: &serde_json::Number) -> Option<JsonNumber> {
+        match (number.as_i64(), number.as_u64()) {
+            (Some(integer), _) => Some(JsonNumber::Integer(integer as i128)),
+            (None, Some(integer)) => Some(JsonNumber::Integer(integer as i128)),
+            (None, None) => number.as_f64().map(JsonNumber::Float),
+        }
+    }
+
+    fn is_less_than(self, other: JsonNumber) -> bool {
+        match (self, other) {
+            (JsonNumber::Integer(integer), JsonNumber::Integer(other)) => integer < other,
+            _ => self.as_f64() < other.as_f64(),
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            JsonNumber::Integer(integer) => integer as f64,
+            JsonNumber::Float(float) => float,
+        }
+    }
+}
+
+impl std::fmt::Display for JsonNumber {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            JsonNumber::Integer(integer) => write!(formatter, "{integer}"),
+            JsonNumber::Float(float) => write!(formatter, "{float}"),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct ValueSummary {
+    depth: usize,
+    arrays: usize,
+    longest_array: usize,
+    numbers: usize,
+    minimum: Option<JsonNumber>,
+    maximum: Option<JsonNumber>,
+    strings: usize,
+}
+
+impl ValueSummary {
+    fn add(&mut self, value: &Value, depth: usize) {
+        self.depth = self.depth.max(depth);
+        match value {
+            Value::Array(elements) => {
+                self.arrays += 1;
+                self.longest_array = self.longest_array.max(elements.len());
+                for element in elements {
+                    self.add(element, depth + 1);
+                }
+            }
+            Value::Object(fields) => {
+                for field in fields.values() {
+                    self.add(field, depth + 1);
+                }
+            }
+            Value::Number(number) => {
+                if let Some(number) = JsonNumber::from_number(number) {
+                    self.numbers += 1;
+                    if self
+                        .minimum
+                        .is_none_or(|minimum| number.is_less_than(minimum))
+                    {
+                        self.minimum = Some(number);
+                    }
+                    if self
+                        .maximum
+                        .is_none_or(|maximum| maximum.is_less_than(number))
+                    {
+                        self.maximum = Some(number);
+                    }
+                }
+            }
+            Value::String(_) => self.strings += 1,
+            Value::Bool(_) | Value::Null => {}
+        }
+    }
+
+    fn describe(&self) -> String {
+        let mut parts = vec![format!("depth {}", self.depth)];
+        if self.arrays > 0 {
+            parts.push(format!(
+                "{} arrays (longest {})",
+                self.arrays, self.longest_array
+            ));
+        }
+        if let (Some(minimum), Some(maximum)) = (self.minimum, self.maximum) {
+            parts.push(format!(
+                "{} numbers from {minimum} to {maximum}",
+                self.numbers
+            ));
+        }
+        if self.strings > 0 {
+            parts.push(format!("{} strings", self.strings));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Summarises each field of a parsed day from its JSON form.
+fn summarize_parsed(parsed: &Value) -> String {
+    let fields = match parsed {
+        Value::Object(fields) => fields.iter().collect::<Vec<_>>(),
+        _ => return String::from("Parsed structure is not a struct"),
+    };
+    let mut report = vec![String::from("Parsed structure")];
+    for (name, value) in fields {
+        let mut value_summary = ValueSummary::default();
+        value_summary.add(value, 0);
+        report.push(format!("  {name}: {}", value_summary.describe()));
+    }
+    report.join("\n")
+}
+
+pub fn inspect_input(inspect_params: &InspectParams) -> Result<(), Box<dyn Error>> {
+    let puzzle_input = read_puzzle_input(&inspect_params.input_path)?;
+    println!("{}", profile_input(&puzzle_input));
+
+    if let Some((year_of_puzzle, day_to_run)) = &inspect_params.puzzle_to_parse {
+        let advent_day = find_puzzle(year_of_puzzle, day_to_run)?.parse_input(&puzzle_input)?;
+        let parsed = serde_json::from_str(&advent_day.to_json_pretty()?)?;
+        println!();
+        println!("{}", summarize_parsed(&parsed));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_keep_signs_but_not_hyphens() {
+        let expected = vec!["-3", "12", "7"];
+
+        let actual = find_numbers("x=-3, seed-12 to 7");
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn number_summary_flags_values_past_u32() {
+        let expected = Some(NumberSummary {
+            count: 4,
+            range: Some((-5, 4294967296)),
+            most_digits: 10,
+            most_per_line: 3,
+            overflowing: 0,
+        });
+
+        let actual = summarize_numbers(&["1 2 -5", "4294967296"]);
+
+        assert!(actual.as_ref().is_some_and(NumberSummary::exceeds_u32));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn number_summary_flags_large_negative_values() {
+        let flagged =
+            |lines: &[&str]| summarize_numbers(lines).is_some_and(|summary| summary.exceeds_u32());
+
+        assert!(flagged(&["1 -5000000000"]));
+        assert!(!flagged(&["1 -2147483648"]));
+    }
+
+    #[test]
+    fn profile_describes_grids() {
+        let expected = "\
+Lines        3
+Sections     1
+Final line   ends with a newline
+Grid         3 x 3
+Characters   '.' 6, '#' 2, '1' 1
+Numbers      1 from 1 to 1, up to 1 digits, up to 1 per line
+Exceeds u32  no";
+
+        let actual = profile_input("#..\n.1.\n..#\n");
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn overflowing_numbers_are_still_counted() {
+        let huge = "9".repeat(40);
+        let expected = "\
+Numbers      2, up to 40 digits, up to 1 per line
+             2 too large for i128 left out of the range
+Exceeds u32  yes";
+
+        let actual = profile_input(&format!("{huge}\n{huge}\n"));
+
+        assert!(actual.ends_with(expected));
+    }
+
+    #[test]
+    fn parsed_summary_keeps_large_integers_exact() {
+        let expected = "\
+Parsed structure
+  seeds: depth 1, 1 arrays (longest 2), 2 numbers from 18446744073709551614 to 18446744073709551615";
+
+        let parsed = serde_json::json!({ "seeds": [u64::MAX, u64::MAX - 1] });
+        let actual = summarize_parsed(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parsed_summary_covers_each_field() {
+        let expected = "\
+Parsed structure
+  name: depth 0, 1 strings
+  parsed_input: depth 2, 3 arrays (longest 2), 3 numbers from -1 to 4";
+
+        let parsed = serde_json::json!({
+            "parsed_input": [[1, -1], [4]],
+            "name": "day9"
+        });
+        let actual = summarize_parsed(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+}
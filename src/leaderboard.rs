@@ -0,0 +1,325 @@
+use std::{collections::HashMap, error::Error, fs};
+
+use serde::Deserialize;
+
+use crate::chart::sparkline;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+/// Puzzles unlock at midnight US Eastern time, which is 05:00 UTC in December.
+const UNLOCK_HOUR_UTC: i64 = 5;
+
+pub struct LeaderboardParams {
+    pub export_path: String,
+}
+
+impl LeaderboardParams {
+    pub fn build(
+        mut args: impl Iterator<Item = String>,
+    ) -> Result<LeaderboardParams, &'static str> {
+        let export_path = args
+            .next()
+            .ok_or("Path to leaderboard JSON export not provided!")?;
+        if args.next().is_some() {
+            return Err("Unrecognised flag provided!");
+        }
+        Ok(LeaderboardParams { export_path })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StarCompletion {
+    get_star_ts: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Member {
+    id: u64,
+    name: Option<String>,
+    #[serde(default)]
+    completion_day_level: HashMap<String, HashMap<String, StarCompletion>>,
+}
+
+impl Member {
+    fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    fn star_time(&self, day: u32, part: u32) -> Option<i64> {
+        self.completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())
+            .map(|completion| completion.get_star_ts)
+    }
+
+    fn star_count(&self) -> usize {
+        self.completion_day_level.values().map(HashMap::len).sum()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LeaderboardExport {
+    event: String,
+    members: HashMap<String, Member>,
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn unlock_time(year: i64, day: u32) -> i64 {
+    days_from_civil(year, 12, day as i64) * SECONDS_PER_DAY + UNLOCK_HOUR_UTC * 60 * 60
+}
+
+fn format_duration(seconds: i64) -> String {
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+struct LeaderboardAnalysis {
+    members: Vec<Member>,
+    days: Vec<u32>,
+    /// Each member's cumulative local score after each day, indexed like `members` and `days`.
+    cumulative_points: Vec<Vec<u64>>,
+}
+
+impl LeaderboardAnalysis {
+    fn new(leaderboard_export: LeaderboardExport) -> LeaderboardAnalysis {
+        let mut members = leaderboard_export.members.into_values().collect::<Vec<_>>();
+        members.sort_by_key(|member| member.id);
+        let mut days = members
+            .iter()
+            .flat_map(|member| member.completion_day_level.keys())
+            .filter_map(|day| day.parse::<u32>().ok())
+            .collect::<Vec<_>>();
+        days.sort();
+        days.dedup();
+
+        // Each star is worth one point per member, less one for every member who got it earlier.
+        let mut points = vec![0; members.len()];
+        let mut cumulative_points = vec![vec![]; members.len()];
+        for &day in &days {
+            for part in 1..=2 {
+                let mut finishers = members
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, member)| Some((member.star_time(day, part)?, index)))
+                    .collect::<Vec<_>>();
+                finishers.sort();
+                for (position, (_, index)) in finishers.into_iter().enumerate() {
+                    points[index] += (members.len() - position) as u64;
+                }
+            }
+            for (index, member_points) in points.iter().enumerate() {
+                cumulative_points[index].push(*member_points);
+            }
+        }
+
+        LeaderboardAnalysis {
+            members,
+            days,
+            cumulative_points,
+        }
+    }
+
+    fn final_points(&self, index: usize) -> u64 {
+        self.cumulative_points[index].last().copied().unwrap_or(0)
+    }
+
+    /// Member indexes ordered by their points after the day at `day_index`.
+    fn standings_after(&self, day_index: usize) -> Vec<usize> {
+        let mut standings = (0..self.members.len()).collect::<Vec<_>>();
+        standings.sort_by_key(|&index| {
+            (
+                std::cmp::Reverse(self.cumulative_points[index][day_index]),
+                self.members[index].id,
+            )
+        });
+        standings
+    }
+
+    fn rank_history(&self, member_index: usize) -> Vec<usize> {
+        (0..self.days.len())
+            .map(|day_index| {
+                self.standings_after(day_index)
+                    .iter()
+                    .position(|&index| index == member_index)
+                    .unwrap_or(0)
+                    + 1
+            })
+            .collect()
+    }
+
+    fn format_report(&self, year: i64) -> String {
+        let mut report = vec![];
+        let name_width = self
+            .members
+            .iter()
+            .map(|member| member.display_name().chars().count())
+            .max()
+            .unwrap_or(0);
+        let final_standings = match self.days.len() {
+            0 => (0..self.members.len()).collect(),
+            day_count => self.standings_after(day_count - 1),
+        };
+
+        report.push(String::from("Standings"));
+        for (rank, &index) in final_standings.iter().enumerate() {
+            report.push(format!(
+                "{:>3}. {:name_width$}  {:>2} stars  {:>5} points",
+                rank + 1,
+                self.members[index].display_name(),
+                self.members[index].star_count(),
+                self.final_points(index),
+            ));
+        }
+
+        for &day in &self.days {
+            report.push(String::new());
+            report.push(format!(
+                "{:<8}{:name_width$}  {:>10}  {:>10}  {:>10}",
+                format!("Day {day}"),
+                "",
+                "part 1",
+                "part 2",
+                "delta"
+            ));
+            let mut finishers = final_standings
+                .iter()
+                .map(|&index| &self.members[index])
+                .filter_map(|member| Some((member.star_time(day, 1)?, member)))
+                .collect::<Vec<_>>();
+            finishers.sort_by_key(|(first_star, member)| (*first_star, member.id));
+            for (first_star, member) in finishers {
+                let unlocked_at = unlock_time(year, day);
+                let second_star = member.star_time(day, 2);
+                report.push(format!(
+                    "        {:name_width$}  {:>10}  {:>10}  {:>10}",
+                    member.display_name(),
+                    format_duration(first_star - unlocked_at),
+                    second_star.map_or(String::from("-"), |second_star| format_duration(
+                        second_star - unlocked_at
+                    )),
+                    second_star.map_or(String::from("-"), |second_star| format_duration(
+                        second_star - first_star
+                    )),
+                ));
+            }
+        }
+
+        report.push(String::new());
+        report.push(format!(
+            "Rank after each day ({})",
+            self.days
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        for &index in &final_standings {
+            report.push(format!(
+                "  {:name_width$}  {}",
+                self.members[index].display_name(),
+                self.rank_history(index)
+                    .iter()
+                    .map(|rank| format!("{rank:>2}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ));
+        }
+
+        let maximum_points = (0..self.members.len())
+            .map(|index| self.final_points(index))
+            .max()
+            .unwrap_or(0);
+        report.push(String::new());
+        report.push(String::from("Points over time"));
+        for &index in &final_standings {
+            report.push(format!(
+                "  {:name_width$}  {} {}",
+                self.members[index].display_name(),
+                sparkline(&self.cumulative_points[index], maximum_points),
+                self.final_points(index)
+            ));
+        }
+        report.join("\n")
+    }
+}
+
+fn analyse_leaderboard(export: &str) -> Result<String, Box<dyn Error>> {
+    let leaderboard_export: LeaderboardExport = serde_json::from_str(export)?;
+    let year = leaderboard_export
+        .event
+        .parse()
+        .map_err(|_| "Leaderboard event must be a year")?;
+    Ok(LeaderboardAnalysis::new(leaderboard_export).format_report(year))
+}
+
+pub fn show_leaderboard(leaderboard_params: &LeaderboardParams) -> Result<(), Box<dyn Error>> {
+    let export = fs::read_to_string(&leaderboard_params.export_path)?;
+    println!("{}", analyse_leaderboard(&export)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Day 1 2023 unlocked at 1701406800.
+    const EXPORT: &str = r#"{
+        "event": "2023",
+        "owner_id": 1,
+        "members": {
+            "1": {"id": 1, "name": "alice", "stars": 3, "local_score": 0,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1701406900, "star_index": 0},
+                          "2": {"get_star_ts": 1701407000, "star_index": 1}},
+                    "2": {"1": {"get_star_ts": 1701494000, "star_index": 4}}}},
+            "2": {"id": 2, "name": null, "stars": 4, "local_score": 0,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1701406850, "star_index": 2},
+                          "2": {"get_star_ts": 1701410400, "star_index": 3}},
+                    "2": {"1": {"get_star_ts": 1701493300, "star_index": 5},
+                          "2": {"get_star_ts": 1701493400, "star_index": 6}}}}
+        }
+    }"#;
+
+    #[test]
+    fn points_and_ranks_follow_star_order() {
+        let leaderboard_export: LeaderboardExport = serde_json::from_str(EXPORT).unwrap();
+        let analysis = LeaderboardAnalysis::new(leaderboard_export);
+
+        assert_eq!(vec![1, 2], analysis.days);
+        assert_eq!(vec![3, 4], analysis.cumulative_points[0]);
+        assert_eq!(vec![3, 7], analysis.cumulative_points[1]);
+        assert_eq!(vec![1, 2], analysis.rank_history(0));
+        assert_eq!(vec![2, 1], analysis.rank_history(1));
+    }
+
+    #[test]
+    fn report_shows_times_and_deltas() -> Result<(), Box<dyn Error>> {
+        let report = analyse_leaderboard(EXPORT)?;
+
+        assert!(report.contains("  1. (anonymous user #2)   4 stars      7 points"));
+        assert!(report.contains("alice                   0:01:40     0:03:20     0:01:40"));
+        assert!(report.contains("(anonymous user #2)     0:00:50     1:00:00     0:59:10"));
+        Ok(())
+    }
+
+    #[test]
+    fn unlock_time_is_midnight_eastern() {
+        assert_eq!(1701406800, unlock_time(2023, 1));
+        assert_eq!(1702962000, unlock_time(2023, 19));
+    }
+}
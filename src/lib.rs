@@ -1,19 +1,187 @@
 mod advent;
+mod animation;
+mod api;
+mod batch;
+mod budget;
+mod cache;
+mod chart;
+mod compare;
+mod detect;
+mod dump;
+mod extract;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod history;
+mod inputs;
+mod inspect;
+mod leaderboard;
+mod logging;
+mod profiles;
+mod repl;
+mod server;
+mod show;
 use std::collections::HashMap;
 use std::error::Error;
+use std::fs;
+use std::time::{Duration, Instant};
 
+#[cfg(feature = "y2022")]
 use advent::y2022;
+#[cfg(feature = "y2023")]
 use advent::y2023;
-use advent::Parse;
+use advent::{letters, Parse, Trace};
+use api::Strategy;
+use cache::{AnswerCache, DayKey};
+use extract::read_answers;
+use history::{HistoryRecord, RunSource, BOTH_PARTS};
+use inputs::read_puzzle_input;
+use profiles::Profile;
+use tracing::info_span;
+
+pub use advent::TraceLevel;
+pub use api::{puzzle, puzzles, AdventError, Answer, Answers, ParsedPuzzle, Part, Puzzle};
+pub use budget::BudgetParams;
+pub use cache::CacheParams;
+pub use compare::CompareParams;
+pub use detect::DetectParams;
+pub use dump::ParseParams;
+pub use extract::ExtractParams;
+pub use history::HistoryParams;
+pub use inputs::{set_key_path, InputsParams};
+pub use inspect::InspectParams;
+pub use leaderboard::LeaderboardParams;
+pub use logging::{init_logging, LogParams, LoggingGuard};
+pub use profiles::ProfilesParams;
+pub use repl::ReplParams;
+pub use server::ServeParams;
+pub use show::ShowParams;
+
+pub enum Command {
+    Run(CLIParams),
+    Serve(ServeParams),
+    Repl(ReplParams),
+    Parse(ParseParams),
+    Compare(CompareParams),
+    Inputs(InputsParams),
+    Show(ShowParams),
+    Extract(ExtractParams),
+    Leaderboard(LeaderboardParams),
+    History(HistoryParams),
+    Inspect(InspectParams),
+    Budget(BudgetParams),
+    Profiles(ProfilesParams),
+    Cache(CacheParams),
+    Detect(DetectParams),
+}
+
+impl Command {
+    pub fn build(args: impl Iterator<Item = String>) -> Result<Command, &'static str> {
+        let mut args = args.peekable();
+        let program_name = args.next();
+
+        match args.peek().map(String::as_str) {
+            Some("serve") => {
+                args.next();
+                Ok(Command::Serve(ServeParams::build(args)?))
+            }
+            Some("repl") => {
+                args.next();
+                Ok(Command::Repl(ReplParams::build(args)?))
+            }
+            Some("parse") => {
+                args.next();
+                Ok(Command::Parse(ParseParams::build(args)?))
+            }
+            Some("compare") => {
+                args.next();
+                Ok(Command::Compare(CompareParams::build(args)?))
+            }
+            Some("inputs") => {
+                args.next();
+                Ok(Command::Inputs(InputsParams::build(args)?))
+            }
+            Some("show") => {
+                args.next();
+                Ok(Command::Show(ShowParams::build(args)?))
+            }
+            Some("extract") => {
+                args.next();
+                Ok(Command::Extract(ExtractParams::build(args)?))
+            }
+            Some("leaderboard") => {
+                args.next();
+                Ok(Command::Leaderboard(LeaderboardParams::build(args)?))
+            }
+            Some("history") => {
+                args.next();
+                Ok(Command::History(HistoryParams::build(args)?))
+            }
+            Some("inspect") => {
+                args.next();
+                Ok(Command::Inspect(InspectParams::build(args)?))
+            }
+            Some("budget") => {
+                args.next();
+                Ok(Command::Budget(BudgetParams::build(args)?))
+            }
+            Some("profiles") => {
+                args.next();
+                Ok(Command::Profiles(ProfilesParams::build(args)?))
+            }
+            Some("cache") => {
+                args.next();
+                Ok(Command::Cache(CacheParams::build(args)?))
+            }
+            Some("detect") => {
+                args.next();
+                Ok(Command::Detect(DetectParams::build(args)?))
+            }
+            _ => Ok(Command::Run(CLIParams::build(
+                program_name.into_iter().chain(args),
+            )?)),
+        }
+    }
+}
+
+pub fn run_command(command: &Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Run(cli_params) => run_advent_day(cli_params),
+        Command::Serve(serve_params) => server::serve(serve_params),
+        Command::Repl(repl_params) => repl::run_repl(repl_params),
+        Command::Parse(parse_params) => dump::dump_parsed_input(parse_params),
+        Command::Compare(compare_params) => compare::compare_with_reference(compare_params),
+        Command::Inputs(inputs_params) => inputs::run_inputs_command(inputs_params),
+        Command::Show(show_params) => show::show_puzzle(show_params),
+        Command::Extract(extract_params) => extract::extract_examples(extract_params),
+        Command::Leaderboard(leaderboard_params) => {
+            leaderboard::show_leaderboard(leaderboard_params)
+        }
+        Command::History(history_params) => history::show_history(history_params),
+        Command::Inspect(inspect_params) => inspect::inspect_input(inspect_params),
+        Command::Budget(budget_params) => budget::run_budget(budget_params),
+        Command::Profiles(profiles_params) => profiles::run_profiles_command(profiles_params),
+        Command::Cache(cache_params) => cache::run_cache_command(cache_params),
+        Command::Detect(detect_params) => detect::detect_puzzle(detect_params),
+    }
+}
 
 pub struct CLIParams {
     pub year_of_puzzle: String,
     pub day_to_run: String,
-    pub input_path: String,
+    /// Taken from the profile's inputs when not given.
+    pub input_path: Option<String>,
+    pub profile: Option<String>,
+    pub use_cache: bool,
+    pub trace_level: TraceLevel,
+    pub trace_output: Option<String>,
+    pub visualize_part: Option<u8>,
+    pub frame_delay_ms: u64,
+    pub start_paused: bool,
 }
 
 impl CLIParams {
-    pub fn build(mut args: impl Iterator<Item = String>) -> Result<CLIParams, &'static str> {
+    pub fn build(args: impl Iterator<Item = String>) -> Result<CLIParams, &'static str> {
+        let mut args = args.peekable();
         args.next();
 
         let year_of_puzzle = match args.next() {
@@ -26,52 +194,361 @@ impl CLIParams {
             None => return Err("Day to run not provided!"),
         };
 
-        let input_path = match args.next() {
-            Some(input_path) => input_path,
-            None => return Err("Path to puzzle input not provided!"),
-        };
+        let input_path = args.next_if(|arg| !arg.starts_with("--"));
+
+        let mut profile = None;
+        let mut use_cache = true;
+        let mut trace_level = TraceLevel::Off;
+        let mut trace_output = None;
+        let mut visualize_part = None;
+        let mut frame_delay_ms = 200;
+        let mut start_paused = false;
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "--trace" => {
+                    trace_level = args
+                        .next()
+                        .ok_or("Trace level not provided after --trace!")?
+                        .parse()?;
+                }
+                "--trace-output" => {
+                    trace_output = Some(
+                        args.next()
+                            .ok_or("Path to trace output not provided after --trace-output!")?,
+                    );
+                    if trace_level == TraceLevel::Off {
+                        trace_level = TraceLevel::Steps;
+                    }
+                }
+                "--visualize" => {
+                    let part = args
+                        .next()
+                        .ok_or("Part to visualize not provided after --visualize!")?;
+                    visualize_part = match part.as_str() {
+                        "1" => Some(1),
+                        "2" => Some(2),
+                        _ => return Err("Part to visualize must be 1 or 2!"),
+                    };
+                }
+                "--frame-delay" => {
+                    frame_delay_ms = args
+                        .next()
+                        .ok_or("Frame delay not provided after --frame-delay!")?
+                        .parse()
+                        .map_err(|_| "Frame delay must be a whole number of milliseconds!")?;
+                }
+                "--paused" => start_paused = true,
+                "--no-cache" => use_cache = false,
+                "--profile" => {
+                    profile = Some(
+                        args.next()
+                            .ok_or("Profile name not provided after --profile!")?,
+                    );
+                }
+                _ => return Err("Unrecognised flag provided!"),
+            }
+        }
+        if input_path.is_none() && profile.is_none() {
+            return Err("Path to puzzle input not provided!");
+        }
 
-        return Ok(CLIParams {
+        Ok(CLIParams {
             year_of_puzzle,
             day_to_run,
             input_path,
-        });
+            profile,
+            use_cache,
+            trace_level,
+            trace_output,
+            visualize_part,
+            frame_delay_ms,
+            start_paused,
+        })
     }
 }
 
+/// Every year with solutions in the source tree, whether or not its feature is enabled.
+const YEARS_WITH_SOLUTIONS: [&str; 2] = ["2022", "2023"];
+
+const YEAR_NOT_BUILT_ERROR_MESSAGE: &str =
+    "Specified year was not built into this binary, rebuild with its cargo feature enabled";
+
 fn collect_puzzles() -> HashMap<&'static str, HashMap<&'static str, Box<dyn Parse>>> {
-    let mut puzzle_days_2022: HashMap<&'static str, Box<dyn Parse>> = HashMap::new();
-    let mut puzzle_days_2023: HashMap<&'static str, Box<dyn Parse>> = HashMap::new();
-
-    puzzle_days_2022.insert("day1", Box::new(y2022::NewDay1Puzzle {}));
-    puzzle_days_2022.insert("day2", Box::new(y2022::NewDay2Puzzle {}));
-    puzzle_days_2022.insert("day3", Box::new(y2022::NewDay3Puzzle {}));
-    puzzle_days_2022.insert("day4", Box::new(y2022::NewDay4Puzzle {}));
-    puzzle_days_2022.insert("day5", Box::new(y2022::NewDay5Puzzle {}));
-    puzzle_days_2023.insert("day1", Box::new(y2023::NewDay1Puzzle {}));
-    puzzle_days_2023.insert("day2", Box::new(y2023::NewDay2Puzzle {}));
-    puzzle_days_2023.insert("day3", Box::new(y2023::NewDay3Puzzle {}));
-    puzzle_days_2023.insert("day4", Box::new(y2023::NewDay4Puzzle {}));
-    puzzle_days_2023.insert("day5", Box::new(y2023::NewDay5Puzzle {}));
-    puzzle_days_2023.insert("day6", Box::new(y2023::NewDay6Puzzle {}));
-    puzzle_days_2023.insert("day7", Box::new(y2023::NewDay7Puzzle {}));
-    puzzle_days_2023.insert("day8", Box::new(y2023::NewDay8Puzzle {}));
-    puzzle_days_2023.insert("day9", Box::new(y2023::NewDay9Puzzle {}));
-
-    let puzzle_days = HashMap::from([("2022", puzzle_days_2022), ("2023", puzzle_days_2023)]);
+    #[allow(unused_mut)]
+    let mut puzzle_days = HashMap::new();
+
+    #[cfg(feature = "y2022")]
+    {
+        let mut puzzle_days_2022: HashMap<&'static str, Box<dyn Parse>> = HashMap::new();
+        puzzle_days_2022.insert("day1", Box::new(y2022::NewDay1Puzzle {}));
+        puzzle_days_2022.insert("day2", Box::new(y2022::NewDay2Puzzle {}));
+        puzzle_days_2022.insert("day3", Box::new(y2022::NewDay3Puzzle {}));
+        puzzle_days_2022.insert("day4", Box::new(y2022::NewDay4Puzzle {}));
+        puzzle_days_2022.insert("day5", Box::new(y2022::NewDay5Puzzle {}));
+        puzzle_days_2022.insert("day25", Box::new(y2022::NewDay25Puzzle {}));
+        puzzle_days.insert("2022", puzzle_days_2022);
+    }
+
+    #[cfg(feature = "y2023")]
+    {
+        let mut puzzle_days_2023: HashMap<&'static str, Box<dyn Parse>> = HashMap::new();
+        puzzle_days_2023.insert("day1", Box::new(y2023::NewDay1Puzzle {}));
+        puzzle_days_2023.insert("day2", Box::new(y2023::NewDay2Puzzle {}));
+        puzzle_days_2023.insert("day3", Box::new(y2023::NewDay3Puzzle {}));
+        puzzle_days_2023.insert("day4", Box::new(y2023::NewDay4Puzzle {}));
+        puzzle_days_2023.insert("day5", Box::new(y2023::NewDay5Puzzle {}));
+        puzzle_days_2023.insert("day6", Box::new(y2023::NewDay6Puzzle {}));
+        puzzle_days_2023.insert("day7", Box::new(y2023::NewDay7Puzzle {}));
+        puzzle_days_2023.insert("day8", Box::new(y2023::NewDay8Puzzle {}));
+        puzzle_days_2023.insert("day9", Box::new(y2023::NewDay9Puzzle {}));
+        puzzle_days.insert("2023", puzzle_days_2023);
+    }
+
     puzzle_days
 }
 
+fn find_puzzle(year_of_puzzle: &str, day_to_run: &str) -> Result<Box<dyn Parse>, &'static str> {
+    let mut puzzle_days_for_year = match collect_puzzles().remove(year_of_puzzle) {
+        Some(puzzle_days_for_year) => puzzle_days_for_year,
+        None if YEARS_WITH_SOLUTIONS.contains(&year_of_puzzle) => {
+            return Err(YEAR_NOT_BUILT_ERROR_MESSAGE)
+        }
+        None => return Err("Specified year has not been implemented yet"),
+    };
+    puzzle_days_for_year
+        .remove(day_to_run)
+        .ok_or("Specified day to run has not been implemented for that year")
+}
+
+fn list_puzzles() -> Vec<(&'static str, &'static str)> {
+    let mut puzzles = collect_puzzles()
+        .into_iter()
+        .flat_map(|(year, days)| days.into_keys().map(move |day| (year, day)))
+        .collect::<Vec<_>>();
+    puzzles.sort_by_key(|(year, day)| (*year, day.len(), *day));
+    puzzles
+}
+
 pub fn run_advent_day(cli_params: &CLIParams) -> Result<(), Box<dyn Error>> {
-    let mut puzzle_days = collect_puzzles();
-    let puzzle_to_run = puzzle_days
-        .remove(&*cli_params.year_of_puzzle)
-        .ok_or("Specified year has not been implemented yet")?
-        .remove(&*cli_params.day_to_run)
-        .ok_or("Specified day to run has not been implemented for that year")?;
-    let advent_day = puzzle_to_run.parse_input(&*cli_params.input_path)?;
-    println!("Part 1: {}", advent_day.solve_first_puzzle());
-    println!("Part 2: {}", advent_day.solve_second_puzzle());
+    let _run_span = info_span!(
+        "run",
+        year = cli_params.year_of_puzzle,
+        day = cli_params.day_to_run
+    )
+    .entered();
+    let profile = cli_params
+        .profile
+        .as_deref()
+        .map(profiles::load_profile)
+        .transpose()?;
+    let input_path = match (&cli_params.input_path, &profile) {
+        (Some(input_path), _) => input_path.clone(),
+        (None, Some(profile)) => profile
+            .input_path(&cli_params.year_of_puzzle, &cli_params.day_to_run)
+            .ok_or("Profile has no input for that year and day")?,
+        (None, None) => return Err("Path to puzzle input not provided".into()),
+    };
+    if batch::is_multi_input(&input_path) {
+        if cli_params.visualize_part.is_some() || cli_params.trace_level != TraceLevel::Off {
+            return Err("Visualizing and tracing need a single input".into());
+        }
+        return batch::run_many_inputs(
+            &cli_params.year_of_puzzle,
+            &cli_params.day_to_run,
+            &input_path,
+            cli_params.use_cache,
+        );
+    }
+    let puzzle_to_run = find_puzzle(&cli_params.year_of_puzzle, &cli_params.day_to_run)?;
+    let puzzle_input = read_puzzle_input(&input_path)?;
+
+    let day_key = DayKey::new(
+        &cli_params.year_of_puzzle,
+        &cli_params.day_to_run,
+        Strategy::Combined,
+        &puzzle_input,
+    );
+    let mut answer_cache = cli_params.use_cache.then(AnswerCache::open);
+    // Tracing and visualizing need the solvers to run.
+    let cached_answers = answer_cache
+        .as_ref()
+        .filter(|_| {
+            cli_params.visualize_part.is_none() && cli_params.trace_level == TraceLevel::Off
+        })
+        .and_then(|answer_cache| {
+            let first_answer = answer_cache.get(&day_key, 1)??;
+            let second_answer = answer_cache.get(&day_key, 2)?;
+            Some((first_answer.to_string(), second_answer.map(String::from)))
+        });
+    if let Some((first_answer, second_answer)) = cached_answers {
+        print_answers(
+            cli_params,
+            profile.as_ref(),
+            &first_answer,
+            second_answer.as_deref(),
+        );
+        println!("Answers from cache, run with --no-cache to solve again");
+        return Ok(());
+    }
+    let advent_day = info_span!("parse").in_scope(|| puzzle_to_run.parse_input(&puzzle_input))?;
+    if let Some(part) = cli_params.visualize_part {
+        let frames = match part {
+            1 => advent_day.visualize_first_puzzle(),
+            _ => advent_day.visualize_second_puzzle(),
+        }
+        .ok_or("Specified day does not have a visualization")?;
+        animation::play_frames(
+            &frames,
+            Duration::from_millis(cli_params.frame_delay_ms),
+            cli_params.start_paused,
+        )?;
+    }
+
+    let mut first_trace = Trace::new(cli_params.trace_level);
+    let mut second_trace = Trace::new(cli_params.trace_level);
+    let start = Instant::now();
+    let solved_together = info_span!("solve", part = "both")
+        .in_scope(|| advent_day.solve_both_puzzles(&mut first_trace, &mut second_trace));
+    let (first_answer, second_answer, timed_parts) = match solved_together {
+        Some((first_answer, second_answer)) => {
+            let both_answers = format!("{first_answer}\n{second_answer}");
+            (
+                first_answer,
+                Some(second_answer),
+                vec![(BOTH_PARTS, start.elapsed(), both_answers)],
+            )
+        }
+        None => {
+            let start = Instant::now();
+            let first_answer = info_span!("solve", part = 1)
+                .in_scope(|| advent_day.solve_first_puzzle(&mut first_trace));
+            let mut timed_parts = vec![(1, start.elapsed(), first_answer.clone())];
+            let start = Instant::now();
+            let second_answer = info_span!("solve", part = 2)
+                .in_scope(|| advent_day.solve_second_puzzle(&mut second_trace));
+            if let Some(second_answer) = &second_answer {
+                timed_parts.push((2, start.elapsed(), second_answer.clone()));
+            }
+            (first_answer, second_answer, timed_parts)
+        }
+    };
+
+    // Tracing and animating slow the solvers down, so those runs would skew the baseline.
+    if cli_params.visualize_part.is_none() && cli_params.trace_level == TraceLevel::Off {
+        let history_records = timed_parts
+            .iter()
+            .map(|(part, duration, answer)| {
+                HistoryRecord::new(
+                    &cli_params.year_of_puzzle,
+                    &cli_params.day_to_run,
+                    *part,
+                    *duration,
+                    answer,
+                    RunSource::Single,
+                )
+            })
+            .collect::<Vec<_>>();
+        history::record_runs_or_warn(&history_records);
+    }
+
+    if cli_params.trace_level != TraceLevel::Off {
+        let trace_lines = format_trace_lines(&[(1, &first_trace), (2, &second_trace)]);
+        match &cli_params.trace_output {
+            Some(trace_output) => fs::write(trace_output, trace_lines)?,
+            None => print!("{trace_lines}"),
+        }
+    }
+
+    if let Some(answer_cache) = &mut answer_cache {
+        answer_cache.insert(&day_key, 1, Some(&first_answer));
+        answer_cache.insert(&day_key, 2, second_answer.as_deref());
+        if let Err(err) = answer_cache.save() {
+            tracing::warn!("Failed to save the answer cache: {err}");
+        }
+    }
+
+    print_answers(
+        cli_params,
+        profile.as_ref(),
+        &first_answer,
+        second_answer.as_deref(),
+    );
 
     Ok(())
 }
+
+/// Prints the answers, checked against the profile's answers when one is in use.
+fn print_answers(
+    cli_params: &CLIParams,
+    profile: Option<&Profile>,
+    first_answer: &str,
+    second_answer: Option<&str>,
+) {
+    println!("Part 1: {}", display_answer(first_answer));
+    if let Some(second_answer) = second_answer {
+        println!("Part 2: {}", display_answer(second_answer));
+    }
+    if let (Some(profile), Some(profile_name)) = (profile, &cli_params.profile) {
+        let answer_lines = [Some(first_answer), second_answer]
+            .into_iter()
+            .flatten()
+            .map(|answer| Answer::from_solver_output(answer.to_string()).to_line())
+            .collect::<Vec<_>>();
+        let verdict = profiles::check_answers(
+            &answer_lines,
+            read_answers(&profile.answers_path(&cli_params.year_of_puzzle, &cli_params.day_to_run)),
+        );
+        println!("Profile {profile_name}: {}", verdict.describe());
+    }
+}
+
+/// Puts picture answers on their own lines and adds the letters they spell.
+fn display_answer(answer: &str) -> String {
+    if !letters::is_letter_picture(answer) {
+        return answer.to_string();
+    }
+    match letters::recognize_letters(answer) {
+        Ok(decoded) => format!("{decoded}\n{answer}"),
+        Err(err) => format!("(not decoded: {err})\n{answer}"),
+    }
+}
+
+fn format_trace_lines(traces: &[(u8, &Trace)]) -> String {
+    let mut trace_lines = String::new();
+    for (part, trace) in traces {
+        for event in trace.events() {
+            trace_lines.push_str(&format!("part{part} {event}\n"));
+        }
+    }
+    trace_lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "y2022", feature = "y2023"))]
+    fn list_puzzles_sorted_by_year_then_day() {
+        let puzzles = list_puzzles();
+
+        assert_eq!(Some(&("2022", "day1")), puzzles.first());
+        assert_eq!(Some(&("2022", "day2")), puzzles.get(1));
+        assert_eq!(Some(&("2023", "day9")), puzzles.last());
+    }
+
+    #[test]
+    fn find_puzzle_distinguishes_unbuilt_years() {
+        let expected = if cfg!(feature = "y2022") {
+            "Specified day to run has not been implemented for that year"
+        } else {
+            YEAR_NOT_BUILT_ERROR_MESSAGE
+        };
+
+        assert_eq!(Some(expected), find_puzzle("2022", "day24").err());
+        assert_eq!(
+            Some("Specified year has not been implemented yet"),
+            find_puzzle("2015", "day1").err()
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, IsTerminal},
+    sync::Mutex,
+};
+
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::{
+    filter::LevelFilter, fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt, Layer,
+};
+
+/// Span files always record debug events so a saved run can be inspected in full.
+const SPAN_FILE_LEVEL: LevelFilter = LevelFilter::DEBUG;
+
+/// Logging flags accepted between the program name and the command.
+#[derive(Debug, PartialEq)]
+pub struct LogParams {
+    pub verbosity: i32,
+    pub spans_json_path: Option<String>,
+    pub chrome_trace_path: Option<String>,
+    /// The key file for encrypted inputs. It is not a logging flag, but it goes before the
+    /// command as they do, so they are taken together in any order.
+    pub key_path: Option<String>,
+}
+
+impl LogParams {
+    /// Takes the logging flags and `--key` that come before the command out of `args`,
+    /// returning the program name and the arguments left for the command. Parsing stops at the first
+    /// other argument, or after `--`, so the command's own arguments are never taken.
+    pub fn build(
+        args: impl Iterator<Item = String>,
+    ) -> Result<(LogParams, Vec<String>), &'static str> {
+        let mut log_params = LogParams {
+            verbosity: 0,
+            spans_json_path: None,
+            chrome_trace_path: None,
+            key_path: None,
+        };
+        let mut args = args.peekable();
+        let mut command_args = args.next().into_iter().collect::<Vec<_>>();
+        while let Some(arg) = args.next_if(|arg| is_logging_flag(arg)) {
+            match arg.as_str() {
+                "--" => break,
+                "--spans-json" => {
+                    log_params.spans_json_path = Some(
+                        args.next()
+                            .ok_or("Path to span JSON output not provided after --spans-json!")?,
+                    );
+                }
+                "--chrome-trace" => {
+                    log_params.chrome_trace_path =
+                        Some(args.next().ok_or(
+                            "Path to chrome trace output not provided after --chrome-trace!",
+                        )?);
+                }
+                "--key" => {
+                    log_params.key_path = Some(
+                        args.next()
+                            .ok_or("Path to key file not provided after --key!")?,
+                    );
+                }
+                flag if is_repeated_flag(flag, 'v') => {
+                    log_params.verbosity += flag.len() as i32 - 1
+                }
+                _ => log_params.verbosity -= arg.len() as i32 - 1,
+            }
+        }
+        command_args.extend(args);
+        Ok((log_params, command_args))
+    }
+
+    fn level_filter(&self) -> LevelFilter {
+        match self.verbosity {
+            i32::MIN..=-2 => LevelFilter::OFF,
+            -1 => LevelFilter::ERROR,
+            0 => LevelFilter::WARN,
+            1 => LevelFilter::INFO,
+            2 => LevelFilter::DEBUG,
+            _ => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// Matches flags such as `-v`, `-vv` and `-vvv`.
+fn is_repeated_flag(flag: &str, letter: char) -> bool {
+    flag.strip_prefix('-')
+        .is_some_and(|letters| !letters.is_empty() && letters.chars().all(|c| c == letter))
+}
+
+fn is_logging_flag(arg: &str) -> bool {
+    matches!(arg, "--" | "--spans-json" | "--chrome-trace" | "--key")
+        || is_repeated_flag(arg, 'v')
+        || is_repeated_flag(arg, 'q')
+}
+
+/// Keeps the chrome trace open until the program ends, when it is flushed to disk.
+pub struct LoggingGuard {
+    _chrome_trace: Option<FlushGuard>,
+}
+
+pub fn init_logging(log_params: &LogParams) -> Result<LoggingGuard, Box<dyn Error>> {
+    let stderr_layer = tracing_subscriber::fmt::layer()
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .with_filter(log_params.level_filter());
+
+    let spans_json_layer = match &log_params.spans_json_path {
+        Some(spans_json_path) => Some(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_span_events(FmtSpan::CLOSE)
+                .with_writer(Mutex::new(File::create(spans_json_path)?))
+                .with_filter(SPAN_FILE_LEVEL),
+        ),
+        None => None,
+    };
+
+    let (chrome_trace_layer, chrome_trace) = match &log_params.chrome_trace_path {
+        Some(chrome_trace_path) => {
+            let (layer, guard) = ChromeLayerBuilder::new()
+                .file(chrome_trace_path)
+                .include_args(true)
+                .build();
+            (Some(layer.with_filter(SPAN_FILE_LEVEL)), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(stderr_layer)
+        .with(spans_json_layer)
+        .with(chrome_trace_layer)
+        .try_init()?;
+
+    Ok(LoggingGuard {
+        _chrome_trace: chrome_trace,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn logging_flags_are_taken_before_the_command() -> Result<(), &'static str> {
+        let expected_args = args(&["rust-advent", "2023", "day9", "input.txt"]).collect::<Vec<_>>();
+
+        let (log_params, actual_args) = LogParams::build(args(&[
+            "rust-advent",
+            "-vv",
+            "--chrome-trace",
+            "trace.json",
+            "2023",
+            "day9",
+            "input.txt",
+        ]))?;
+
+        assert_eq!(expected_args, actual_args);
+        assert_eq!(LevelFilter::DEBUG, log_params.level_filter());
+        assert_eq!(
+            Some(String::from("trace.json")),
+            log_params.chrome_trace_path
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn key_flag_is_taken_with_logging_flags_in_any_order() -> Result<(), &'static str> {
+        let expected_args =
+            args(&["rust-advent", "2023", "day9", "input.txt.enc"]).collect::<Vec<_>>();
+
+        for flags in [["--key", "team.key", "-v"], ["-v", "--key", "team.key"]] {
+            let (log_params, actual_args) =
+                LogParams::build(args(&["rust-advent"]).chain(args(&flags)).chain(args(&[
+                    "2023",
+                    "day9",
+                    "input.txt.enc",
+                ])))?;
+
+            assert_eq!(expected_args, actual_args);
+            assert_eq!(Some(String::from("team.key")), log_params.key_path);
+            assert_eq!(1, log_params.verbosity);
+        }
+        assert!(LogParams::build(args(&["rust-advent", "-v", "--key"])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn command_arguments_are_left_alone() -> Result<(), &'static str> {
+        let expected_args =
+            args(&["rust-advent", "2023", "day9", "-vv", "--spans-json"]).collect::<Vec<_>>();
+
+        let (log_params, actual_args) = LogParams::build(args(&[
+            "rust-advent",
+            "2023",
+            "day9",
+            "-vv",
+            "--spans-json",
+        ]))?;
+        assert_eq!(expected_args, actual_args);
+        assert_eq!(0, log_params.verbosity);
+
+        let (log_params, actual_args) =
+            LogParams::build(args(&["rust-advent", "-v", "--", "-vv"]))?;
+        assert_eq!(vec!["rust-advent", "-vv"], actual_args);
+        assert_eq!(1, log_params.verbosity);
+        Ok(())
+    }
+
+    #[test]
+    fn quiet_flags_lower_the_level() -> Result<(), &'static str> {
+        let (log_params, _) = LogParams::build(args(&["rust-advent", "-q"]))?;
+        assert_eq!(LevelFilter::ERROR, log_params.level_filter());
+
+        let (log_params, _) = LogParams::build(args(&["rust-advent", "-v", "-qqq"]))?;
+        assert_eq!(LevelFilter::OFF, log_params.level_filter());
+        Ok(())
+    }
+}
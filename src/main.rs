@@ -2,15 +2,24 @@ use std::env;
 use std::error::Error;
 use std::process;
 
-use rust_advent::{run_advent_day, CLIParams};
+use rust_advent::{init_logging, run_command, set_key_path, Command, LogParams};
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let cli_params = CLIParams::build(env::args()).unwrap_or_else(|err| {
+    let (log_params, args) = LogParams::build(env::args()).unwrap_or_else(|err| {
         eprintln!("Problem parsing arguments: {err}");
         process::exit(1);
     });
+    let _logging_guard = init_logging(&log_params)?;
+    if let Some(key_path) = &log_params.key_path {
+        set_key_path(key_path.clone());
+    }
 
-    run_advent_day(&cli_params)?;
+    let command = Command::build(args.into_iter()).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    run_command(&command)?;
 
     Ok(())
 }
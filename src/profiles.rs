@@ -0,0 +1,478 @@
+use std::{collections::BTreeMap, env, error::Error, fs, time::Instant};
+
+use serde::Deserialize;
+
+use crate::api::{self, Answer, Part, Puzzle, Strategy};
+use crate::cache::{AnswerCache, DayKey};
+use crate::extract::read_answers;
+use crate::history::{self, HistoryRecord, RunSource, BOTH_PARTS};
+use crate::inputs::{collection_input_path, read_puzzle_input};
+
+const DEFAULT_CONFIG_PATH: &str = "advent.json";
+const CONFIG_PATH_VARIABLE: &str = "ADVENT_CONFIG";
+
+/// One person's inputs, answers and session token, e.g.
+/// `{"inputs": "inputs/alice", "answers": "answers/alice", "token": "env:ALICE_TOKEN"}`.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Directory of inputs laid out as `<year>/<day>.txt`, encrypted or not.
+    pub inputs: String,
+    /// Directory of answers files laid out as `<year>/<day>.answers`.
+    pub answers: String,
+    /// The session token itself, `env:<variable>` or `file:<path>`.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+impl Profile {
+    pub fn input_path(&self, year: &str, day: &str) -> Option<String> {
+        collection_input_path(&self.inputs, year, day)
+    }
+
+    pub fn answers_path(&self, year: &str, day: &str) -> String {
+        format!("{}/{year}/{day}.answers", self.answers)
+    }
+
+    /// The session token, read from the environment or a file when the profile names one.
+    pub fn session_token(&self) -> Result<Option<String>, Box<dyn Error>> {
+        let Some(token) = &self.token else {
+            return Ok(None);
+        };
+        let session_token = if let Some(variable) = token.strip_prefix("env:") {
+            env::var(variable).map_err(|_| format!("Token variable {variable} is not set"))?
+        } else if let Some(token_path) = token.strip_prefix("file:") {
+            fs::read_to_string(token_path)
+                .map_err(|err| format!("Failed to read token file {token_path}: {err}"))?
+        } else {
+            token.clone()
+        };
+        Ok(Some(session_token.trim().to_string()))
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+}
+
+/// The config file named by `ADVENT_CONFIG`, or `advent.json` in the working directory.
+fn config_path() -> String {
+    env::var(CONFIG_PATH_VARIABLE).unwrap_or_else(|_| String::from(DEFAULT_CONFIG_PATH))
+}
+
+fn read_config() -> Result<Config, Box<dyn Error>> {
+    let config_path = config_path();
+    let config = fs::read_to_string(&config_path)
+        .map_err(|err| format!("Failed to read config file {config_path}: {err}"))?;
+    serde_json::from_str(&config)
+        .map_err(|err| format!("Invalid config file {config_path}: {err}").into())
+}
+
+pub fn load_profile(name: &str) -> Result<Profile, Box<dyn Error>> {
+    read_config()?
+        .profiles
+        .remove(name)
+        .ok_or_else(|| format!("No profile named {name} in {}", config_path()).into())
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ProfilesAction {
+    List,
+    Verify {
+        year_of_puzzle: Option<String>,
+        day_to_run: Option<String>,
+        use_cache: bool,
+    },
+}
+
+pub struct ProfilesParams {
+    pub action: ProfilesAction,
+}
+
+impl ProfilesParams {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<ProfilesParams, &'static str> {
+        let action = match args.next().as_deref() {
+            None | Some("list") => ProfilesAction::List,
+            Some("verify") => {
+                let mut positional = vec![];
+                let mut use_cache = true;
+                for arg in args.by_ref() {
+                    match arg.as_str() {
+                        "--no-cache" => use_cache = false,
+                        flag if flag.starts_with("--") => {
+                            return Err("Unrecognised flag provided!")
+                        }
+                        _ => positional.push(arg),
+                    }
+                }
+                if positional.len() > 2 {
+                    return Err("Unrecognised flag provided!");
+                }
+                let mut positional = positional.into_iter();
+                ProfilesAction::Verify {
+                    year_of_puzzle: positional.next(),
+                    day_to_run: positional.next(),
+                    use_cache,
+                }
+            }
+            Some(_) => return Err("Profiles action must be list or verify!"),
+        };
+        if args.next().is_some() {
+            return Err("Unrecognised flag provided!");
+        }
+        Ok(ProfilesParams { action })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        part: usize,
+        answer: String,
+        expected: String,
+    },
+    Failed(String),
+    NoAnswers,
+}
+
+/// Checks answers in part order against the expected ones, where missing expected
+/// answers are parts that have not been solved yet.
+pub fn check_answers(answers: &[String], expected_answers: Option<Vec<String>>) -> Verdict {
+    let Some(expected_answers) = expected_answers else {
+        return Verdict::NoAnswers;
+    };
+    answers
+        .iter()
+        .zip(expected_answers)
+        .enumerate()
+        .find(|(_, (answer, expected))| *answer != expected)
+        .map_or(Verdict::Correct, |(index, (answer, expected))| {
+            Verdict::Wrong {
+                part: index + 1,
+                answer: answer.clone(),
+                expected,
+            }
+        })
+}
+
+impl Verdict {
+    pub fn describe(&self) -> String {
+        match self {
+            Verdict::Correct => String::from("ok"),
+            Verdict::Wrong {
+                part,
+                answer,
+                expected,
+            } => format!("WRONG part {part}: got {answer}, expected {expected}"),
+            Verdict::Failed(message) => format!("FAILED {message}"),
+            Verdict::NoAnswers => String::from("no answers"),
+        }
+    }
+
+    fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Wrong { .. } | Verdict::Failed(_))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct DayCheck {
+    year: &'static str,
+    day: &'static str,
+    verdict: Verdict,
+}
+
+/// Solves from the cache where it can, adding a history record for each day it solves.
+fn solve_to_lines(
+    puzzle: &Puzzle,
+    input_path: &str,
+    answer_cache: Option<&mut AnswerCache>,
+    history_records: &mut Vec<HistoryRecord>,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let puzzle_input = read_puzzle_input(input_path)?;
+    let day_key = DayKey::new(
+        puzzle.year(),
+        puzzle.day(),
+        Strategy::Combined,
+        &puzzle_input,
+    );
+    let cached_answers = answer_cache.as_deref().and_then(|answer_cache| {
+        Part::ALL
+            .iter()
+            .map(|part| Some(answer_cache.get(&day_key, part.number())?.map(String::from)))
+            .collect::<Option<Vec<_>>>()
+    });
+    let answers = match cached_answers {
+        Some(cached_answers) => cached_answers,
+        None => {
+            let parsed_puzzle = puzzle.parse(&puzzle_input)?;
+            let start = Instant::now();
+            let answers = parsed_puzzle.solve_both()?;
+            let duration = start.elapsed();
+            let answers = [Some(answers.first), answers.second]
+                .into_iter()
+                .map(|answer| answer.map(|answer| answer.to_string()))
+                .collect::<Vec<_>>();
+            history_records.push(HistoryRecord::new(
+                puzzle.year(),
+                puzzle.day(),
+                BOTH_PARTS,
+                duration,
+                &answers
+                    .iter()
+                    .flatten()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                RunSource::Verify,
+            ));
+            if let Some(answer_cache) = answer_cache {
+                for (part, answer) in Part::ALL.iter().zip(&answers) {
+                    answer_cache.insert(&day_key, part.number(), answer.as_deref());
+                }
+            }
+            answers
+        }
+    };
+    Ok(answers
+        .into_iter()
+        .flatten()
+        .map(|answer| Answer::from_solver_output(answer).to_line())
+        .collect())
+}
+
+/// Solves every day the profile has an input for and checks it against its answers.
+fn verify_profile(
+    profile: &Profile,
+    puzzles: &[Puzzle],
+    mut answer_cache: Option<&mut AnswerCache>,
+    history_records: &mut Vec<HistoryRecord>,
+) -> Vec<DayCheck> {
+    puzzles
+        .iter()
+        .filter_map(|puzzle| {
+            let input_path = profile.input_path(puzzle.year(), puzzle.day())?;
+            let solved = solve_to_lines(
+                puzzle,
+                &input_path,
+                answer_cache.as_deref_mut(),
+                history_records,
+            );
+            let verdict = match solved {
+                Ok(answers) => check_answers(
+                    &answers,
+                    read_answers(&profile.answers_path(puzzle.year(), puzzle.day())),
+                ),
+                Err(err) => Verdict::Failed(err.to_string()),
+            };
+            Some(DayCheck {
+                year: puzzle.year(),
+                day: puzzle.day(),
+                verdict,
+            })
+        })
+        .collect()
+}
+
+fn format_verification(profile_checks: &[(String, Vec<DayCheck>)]) -> (String, bool) {
+    let mut lines = vec![];
+    let mut failed = false;
+    let mut correct_days = 0;
+    let mut checked_days = 0;
+    for (name, day_checks) in profile_checks {
+        lines.push(name.clone());
+        if day_checks.is_empty() {
+            lines.push(String::from("  no inputs"));
+        }
+        for day_check in day_checks {
+            failed |= day_check.verdict.is_failure();
+            if day_check.verdict != Verdict::NoAnswers {
+                checked_days += 1;
+            }
+            if day_check.verdict == Verdict::Correct {
+                correct_days += 1;
+            }
+            lines.push(format!(
+                "  {} {:<5}  {}",
+                day_check.year,
+                day_check.day,
+                day_check.verdict.describe()
+            ));
+        }
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "{correct_days} of {checked_days} days verified across {} profiles",
+        profile_checks.len()
+    ));
+    (lines.join("\n"), failed)
+}
+
+fn list_profiles(config: &Config) -> String {
+    if config.profiles.is_empty() {
+        return format!("No profiles in {}", config_path());
+    }
+    config
+        .profiles
+        .iter()
+        .map(|(name, profile)| {
+            let token = match profile.session_token() {
+                Ok(Some(_)) => String::from("token set"),
+                Ok(None) => String::from("no token"),
+                Err(err) => format!("token unreadable ({err})"),
+            };
+            format!(
+                "{name}  inputs {}  answers {}  {token}",
+                profile.inputs, profile.answers
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn run_profiles_command(profiles_params: &ProfilesParams) -> Result<(), Box<dyn Error>> {
+    let config = read_config()?;
+    match &profiles_params.action {
+        ProfilesAction::List => println!("{}", list_profiles(&config)),
+        ProfilesAction::Verify {
+            year_of_puzzle,
+            day_to_run,
+            use_cache,
+        } => {
+            let puzzles = api::puzzles()
+                .into_iter()
+                .filter(|puzzle| {
+                    year_of_puzzle
+                        .as_deref()
+                        .is_none_or(|year| year == puzzle.year())
+                })
+                .filter(|puzzle| day_to_run.as_deref().is_none_or(|day| day == puzzle.day()))
+                .collect::<Vec<_>>();
+            if puzzles.is_empty() {
+                return Err("No registered puzzles match that year and day".into());
+            }
+            let mut answer_cache = use_cache.then(AnswerCache::open);
+            let mut history_records = vec![];
+            let profile_checks = config
+                .profiles
+                .iter()
+                .map(|(name, profile)| {
+                    (
+                        name.clone(),
+                        verify_profile(
+                            profile,
+                            &puzzles,
+                            answer_cache.as_mut(),
+                            &mut history_records,
+                        ),
+                    )
+                })
+                .collect::<Vec<_>>();
+            if let Some(answer_cache) = &answer_cache {
+                if let Err(err) = answer_cache.save() {
+                    tracing::warn!("Failed to save the answer cache: {err}");
+                }
+            }
+            history::record_runs_or_warn(&history_records);
+            let (report, failed) = format_verification(&profile_checks);
+            println!("{report}");
+            if failed {
+                return Err("Some profiles have wrong or failing answers".into());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(answers: &[&str]) -> Vec<String> {
+        answers.iter().map(|answer| answer.to_string()).collect()
+    }
+
+    #[test]
+    fn config_lists_profiles_by_name() {
+        let expected = "\
+alice  inputs inputs/alice  answers answers/alice  token set
+bob  inputs inputs/bob  answers answers/bob  no token
+carol  inputs inputs/carol  answers answers/carol  token unreadable (Token variable ADVENT_TEST_UNSET_TOKEN is not set)";
+
+        let config: Config = serde_json::from_str(
+            r#"{"profiles": {
+                "bob": {"inputs": "inputs/bob", "answers": "answers/bob"},
+                "alice": {"inputs": "inputs/alice", "answers": "answers/alice", "token": "53"},
+                "carol": {"inputs": "inputs/carol", "answers": "answers/carol", "token": "env:ADVENT_TEST_UNSET_TOKEN"}
+            }}"#,
+        )
+        .unwrap();
+        let actual = list_profiles(&config);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn misspelled_profile_keys_are_refused() {
+        let config = serde_json::from_str::<Config>(
+            r#"{"profiles": {"alice": {"inputs": "inputs/alice", "answer": "answers/alice"}}}"#,
+        );
+
+        assert!(config.is_err());
+    }
+
+    #[test]
+    fn answers_are_checked_in_part_order() {
+        assert_eq!(
+            Verdict::Correct,
+            check_answers(&lines(&["114", "2"]), Some(lines(&["114"])))
+        );
+        assert_eq!(
+            Verdict::Wrong {
+                part: 2,
+                answer: String::from("3"),
+                expected: String::from("2")
+            },
+            check_answers(&lines(&["114", "3"]), Some(lines(&["114", "2"])))
+        );
+        assert_eq!(Verdict::NoAnswers, check_answers(&lines(&["114"]), None));
+    }
+
+    #[test]
+    fn verification_fails_on_any_wrong_answer() {
+        let expected = "\
+alice
+  2023 day9   ok
+  2023 day3   WRONG part 1: got 4, expected 5
+bob
+  no inputs
+
+1 of 2 days verified across 2 profiles";
+
+        let profile_checks = vec![
+            (
+                String::from("alice"),
+                vec![
+                    DayCheck {
+                        year: "2023",
+                        day: "day9",
+                        verdict: Verdict::Correct,
+                    },
+                    DayCheck {
+                        year: "2023",
+                        day: "day3",
+                        verdict: check_answers(&lines(&["4"]), Some(lines(&["5"]))),
+                    },
+                ],
+            ),
+            (String::from("bob"), vec![]),
+        ];
+        let (actual, failed) = format_verification(&profile_checks);
+
+        assert_eq!(expected, actual);
+        assert!(failed);
+    }
+}
@@ -0,0 +1,378 @@
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+    time::Instant,
+};
+
+use crate::advent::{Trace, TraceLevel};
+use crate::api::{self, AdventError, Answer, ParsedPuzzle, Part, Strategy};
+use crate::inputs::read_puzzle_input;
+use crate::{display_answer, format_trace_lines};
+
+const REPL_HELP: &str = "\
+Commands:
+  1, 2              solve part 1 or part 2
+  run               solve both parts
+  strategy <name>   how run solves both parts: separate (part by part) or combined
+                    (the day's shared solver, where it has one)
+  params            list the day's parameters and their values
+  set <name> <n>    change a parameter, e.g. set red 20 on 2023 day2
+  show              pretty-print the parsed input
+  trace <lvl>       set the trace level printed after each solve (0/off, 1/steps, 2/detail)
+  reload            re-read and re-parse the input file, keeping the settings
+  help              show this message
+  quit              leave the REPL";
+
+pub struct ReplParams {
+    pub year_of_puzzle: String,
+    pub day_to_run: String,
+    pub input_path: String,
+}
+
+impl ReplParams {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<ReplParams, &'static str> {
+        let year_of_puzzle = args.next().ok_or("Year of puzzle not provided!")?;
+        let day_to_run = args.next().ok_or("Day to run not provided!")?;
+        let input_path = args.next().ok_or("Path to puzzle input not provided!")?;
+        if args.next().is_some() {
+            return Err("Unrecognised flag provided!");
+        }
+
+        Ok(ReplParams {
+            year_of_puzzle,
+            day_to_run,
+            input_path,
+        })
+    }
+}
+
+/// What the user has changed, carried over when the input is reloaded.
+#[derive(Debug)]
+struct Settings {
+    trace_level: TraceLevel,
+    strategy: Strategy,
+    parameters: Vec<(&'static str, u64)>,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            trace_level: TraceLevel::Off,
+            strategy: Strategy::Separate,
+            parameters: vec![],
+        }
+    }
+}
+
+/// A parsed input and the settings used to solve it. The parsed day borrows the input,
+/// so reloading hands the new input back to `run_repl` to start a fresh session.
+struct Session<'a> {
+    year_of_puzzle: String,
+    day_to_run: String,
+    input_path: String,
+    parsed_puzzle: ParsedPuzzle<'a>,
+    settings: Settings,
+}
+
+enum ReplOutcome {
+    Continue(String),
+    Reload(String),
+    Quit,
+}
+
+fn parse_puzzle<'a>(
+    year_of_puzzle: &str,
+    day_to_run: &str,
+    puzzle_input: &'a str,
+) -> Result<ParsedPuzzle<'a>, Box<dyn Error>> {
+    Ok(api::puzzle(year_of_puzzle, day_to_run)?.parse(puzzle_input)?)
+}
+
+fn format_answer(part: u8, answer: &Answer) -> String {
+    format!("Part {part}: {}", display_answer(&answer.to_string()))
+}
+
+impl<'a> Session<'a> {
+    fn load(
+        repl_params: &ReplParams,
+        puzzle_input: &'a str,
+        settings: Settings,
+    ) -> Result<Session<'a>, Box<dyn Error>> {
+        let mut parsed_puzzle = parse_puzzle(
+            &repl_params.year_of_puzzle,
+            &repl_params.day_to_run,
+            puzzle_input,
+        )?;
+        for (name, value) in &settings.parameters {
+            parsed_puzzle.set_parameter(name, *value)?;
+        }
+        Ok(Session {
+            year_of_puzzle: repl_params.year_of_puzzle.clone(),
+            day_to_run: repl_params.day_to_run.clone(),
+            input_path: repl_params.input_path.clone(),
+            parsed_puzzle,
+            settings,
+        })
+    }
+
+    /// Solves a part, reporting a solver that panics instead of ending the session.
+    fn solve(&self, part: Part) -> String {
+        self.solve_if_available(part)
+            .unwrap_or_else(|| format!("Error: {}", AdventError::PartNotAvailable { part }))
+    }
+
+    /// `None` for the missing part 2 of days with only one puzzle.
+    fn solve_if_available(&self, part: Part) -> Option<String> {
+        let mut trace = Trace::new(self.settings.trace_level);
+        let start = Instant::now();
+        let answer = self.parsed_puzzle.solve_traced(part, &mut trace);
+        let elapsed = start.elapsed();
+        let trace_lines = format_trace_lines(&[(part.number(), &trace)]);
+        match answer {
+            Ok(answer) => Some(format!(
+                "{trace_lines}{} ({elapsed:?})",
+                format_answer(part.number(), &answer)
+            )),
+            Err(AdventError::PartNotAvailable { .. }) => None,
+            Err(err) => Some(format!("{trace_lines}Error: {err}")),
+        }
+    }
+
+    fn solve_all(&self) -> String {
+        if self.settings.strategy == Strategy::Combined {
+            let mut first_trace = Trace::new(self.settings.trace_level);
+            let mut second_trace = Trace::new(self.settings.trace_level);
+            let start = Instant::now();
+            let answers = self
+                .parsed_puzzle
+                .solve_combined(&mut first_trace, &mut second_trace);
+            let elapsed = start.elapsed();
+            let trace_lines = format_trace_lines(&[(1, &first_trace), (2, &second_trace)]);
+            match answers {
+                Some(Ok((first, second))) => {
+                    return format!(
+                        "{trace_lines}{}\n{} (both {elapsed:?})",
+                        format_answer(1, &first),
+                        format_answer(2, &second)
+                    )
+                }
+                Some(Err(message)) => return format!("{trace_lines}Error: {message}"),
+                None => {}
+            }
+        }
+        Part::ALL
+            .into_iter()
+            .filter_map(|part| self.solve_if_available(part))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn format_parameters(&self) -> String {
+        let parameters = self.parsed_puzzle.parameters();
+        if parameters.is_empty() {
+            return String::from("This day has no parameters");
+        }
+        parameters
+            .iter()
+            .map(|(name, value)| format!("{name} = {value}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn set_parameter(&mut self, name: &str, value: &str) -> Result<String, Box<dyn Error>> {
+        let value = value
+            .parse::<u64>()
+            .map_err(|_| "Parameter values must be whole numbers")?;
+        self.parsed_puzzle.set_parameter(name, value)?;
+        self.settings.parameters = self.parsed_puzzle.parameters();
+        Ok(format!("{name} set to {value}"))
+    }
+
+    fn execute(&mut self, line: &str) -> Result<ReplOutcome, Box<dyn Error>> {
+        let mut words = line.split_whitespace();
+        let output = match words.next() {
+            None => String::new(),
+            Some("1") => self.solve(Part::One),
+            Some("2") => self.solve(Part::Two),
+            Some("run") => self.solve_all(),
+            Some("strategy") => {
+                self.settings.strategy = match words.next() {
+                    Some("separate") => Strategy::Separate,
+                    Some("combined") => Strategy::Combined,
+                    _ => return Err("Strategy must be separate or combined".into()),
+                };
+                format!("Strategy set to {}", self.settings.strategy.name())
+            }
+            Some("params") => self.format_parameters(),
+            Some("set") => {
+                let (Some(name), Some(value)) = (words.next(), words.next()) else {
+                    return Err("Usage: set <name> <value>".into());
+                };
+                self.set_parameter(name, value)?
+            }
+            Some("show") => format!("{:#?}", self.parsed_puzzle),
+            Some("trace") => {
+                self.settings.trace_level = words
+                    .next()
+                    .ok_or("Trace level not provided after trace!")?
+                    .parse()?;
+                format!("Trace level set to {:?}", self.settings.trace_level)
+            }
+            Some("reload") => {
+                let puzzle_input = read_puzzle_input(&self.input_path)?;
+                // Parsed here too so a broken input is reported and the old one kept.
+                parse_puzzle(&self.year_of_puzzle, &self.day_to_run, &puzzle_input)?;
+                return Ok(ReplOutcome::Reload(puzzle_input));
+            }
+            Some("help") => String::from(REPL_HELP),
+            Some("quit") | Some("exit") => return Ok(ReplOutcome::Quit),
+            Some(_) => return Err("Unrecognised command, type help to list commands".into()),
+        };
+        Ok(ReplOutcome::Continue(output))
+    }
+}
+
+/// Reads commands until the user quits, returning the new input when they reload.
+fn read_commands(session: &mut Session) -> Result<Option<String>, Box<dyn Error>> {
+    let stdin = io::stdin();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        match session.execute(&line) {
+            Ok(ReplOutcome::Continue(output)) if output.is_empty() => {}
+            Ok(ReplOutcome::Continue(output)) => println!("{output}"),
+            Ok(ReplOutcome::Reload(puzzle_input)) => return Ok(Some(puzzle_input)),
+            Ok(ReplOutcome::Quit) => return Ok(None),
+            Err(err) => println!("Error: {err}"),
+        }
+    }
+}
+
+pub fn run_repl(repl_params: &ReplParams) -> Result<(), Box<dyn Error>> {
+    let mut puzzle_input = read_puzzle_input(&repl_params.input_path)?;
+    let mut settings = Settings::default();
+    let mut greeting = format!(
+        "Loaded {} {} from {}. Type help to list commands.",
+        repl_params.year_of_puzzle, repl_params.day_to_run, repl_params.input_path
+    );
+    loop {
+        let (reloaded_input, session_settings) = {
+            let mut session = Session::load(repl_params, &puzzle_input, settings)?;
+            println!("{greeting}");
+            (read_commands(&mut session)?, session.settings)
+        };
+        let Some(reloaded_input) = reloaded_input else {
+            return Ok(());
+        };
+        settings = session_settings;
+        puzzle_input = reloaded_input;
+        greeting = format!("Reloaded {}", repl_params.input_path);
+    }
+}
+
+#[cfg(all(test, feature = "y2023"))]
+mod tests {
+    use super::*;
+
+    fn session_for(day: &str, puzzle_input: &'static str) -> Session<'static> {
+        Session {
+            year_of_puzzle: String::from("2023"),
+            day_to_run: day.to_string(),
+            input_path: String::new(),
+            parsed_puzzle: parse_puzzle("2023", day, puzzle_input).unwrap(),
+            settings: Settings::default(),
+        }
+    }
+
+    fn day_9_session() -> Session<'static> {
+        session_for("day9", "0 3 6 9 12 15")
+    }
+
+    fn output_of(outcome: ReplOutcome) -> String {
+        match outcome {
+            ReplOutcome::Continue(output) => output,
+            ReplOutcome::Reload(_) => panic!("REPL reloaded unexpectedly"),
+            ReplOutcome::Quit => panic!("REPL quit unexpectedly"),
+        }
+    }
+
+    #[test]
+    fn repl_solves_each_part() -> Result<(), Box<dyn Error>> {
+        let mut session = day_9_session();
+
+        assert!(output_of(session.execute("1")?).starts_with("Part 1: 18 ("));
+        assert!(output_of(session.execute("2")?).starts_with("Part 2: -3 ("));
+        Ok(())
+    }
+
+    #[test]
+    fn repl_solves_with_the_combined_strategy() -> Result<(), Box<dyn Error>> {
+        let mut session = day_9_session();
+        session.execute("strategy combined")?;
+
+        let output = output_of(session.execute("run")?);
+
+        assert!(output.starts_with("Part 1: 18\nPart 2: -3 (both "));
+        assert!(session.execute("strategy fastest").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn repl_changes_parameters() -> Result<(), Box<dyn Error>> {
+        let mut session = session_for(
+            "day2",
+            "Game 1: 3 blue, 4 red\nGame 2: 20 red, 1 green\nGame 3: 1 blue",
+        );
+        assert!(output_of(session.execute("1")?).starts_with("Part 1: 4 ("));
+
+        session.execute("set red 20")?;
+
+        assert!(output_of(session.execute("params")?).starts_with("red = 20\n"));
+        assert!(output_of(session.execute("1")?).starts_with("Part 1: 6 ("));
+        assert!(session.execute("set purple 1").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn repl_survives_a_solver_panic() -> Result<(), Box<dyn Error>> {
+        let mut session = session_for("day8", "R\n\nAAA = (BBB, BBB)");
+
+        let output = output_of(session.execute("1")?);
+
+        assert!(output.starts_with("Error: Part 1 failed"));
+        assert!(output_of(session.execute("help")?).starts_with("Commands:"));
+        Ok(())
+    }
+
+    #[test]
+    fn repl_prints_trace_after_enabling_it() -> Result<(), Box<dyn Error>> {
+        let mut session = day_9_session();
+        session.execute("trace steps")?;
+
+        let output = output_of(session.execute("1")?);
+
+        assert!(output.starts_with("part1 prediction sequence=[0, 3, 6, 9, 12, 15] value=18\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn repl_shows_parsed_input() -> Result<(), Box<dyn Error>> {
+        let mut session = day_9_session();
+
+        let output = output_of(session.execute("show")?);
+
+        assert!(output.starts_with("Day9Puzzle {"));
+        Ok(())
+    }
+
+    #[test]
+    fn repl_rejects_unknown_command() {
+        let mut session = day_9_session();
+
+        assert!(session.execute("solve").is_err());
+    }
+}
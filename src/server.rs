@@ -0,0 +1,402 @@
+use std::{
+    error::Error,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::api::{self, AdventError, Answer, ParsedPuzzle, Part};
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+/// Far above any real puzzle input, which stay well under 100 KiB.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+/// How long a client may stall while sending its request or reading the response.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+/// Connections handled at once; further ones wait in the queue.
+const WORKER_COUNT: usize = 8;
+/// The request line and headers together, which real clients keep to a few hundred bytes.
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+/// How long a solve may run before the client is answered with a timeout. The solver
+/// keeps running on its own thread, but its worker moves on to the next connection.
+const SOLVE_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub struct ServeParams {
+    pub address: String,
+}
+
+impl ServeParams {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<ServeParams, &'static str> {
+        let mut address = String::from(DEFAULT_ADDRESS);
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "--address" => {
+                    address = args
+                        .next()
+                        .ok_or("Address to listen on not provided after --address!")?;
+                }
+                "--port" => {
+                    let port = args.next().ok_or("Port not provided after --port!")?;
+                    port.parse::<u16>()
+                        .map_err(|_| "Port must be a number between 0 and 65535!")?;
+                    address = format!("127.0.0.1:{port}");
+                }
+                _ => return Err("Unrecognised flag provided!"),
+            }
+        }
+        Ok(ServeParams { address })
+    }
+}
+
+#[derive(Serialize)]
+struct PuzzleListing {
+    year: &'static str,
+    day: &'static str,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+#[derive(Serialize)]
+struct PartResult {
+    part: u8,
+    answer: Option<String>,
+    decoded: Option<String>,
+    time_us: u128,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SolveResponse {
+    year: String,
+    day: String,
+    parse_time_us: u128,
+    parts: Vec<PartResult>,
+}
+
+struct Response {
+    status: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn json(status: &'static str, body: &impl Serialize) -> Response {
+        Response {
+            status,
+            body: serde_json::to_string_pretty(body)
+                .unwrap_or_else(|_| String::from("{\"error\": \"Failed to serialize response\"}")),
+        }
+    }
+
+    fn error(status: &'static str, error: impl ToString) -> Response {
+        Response::json(
+            status,
+            &ErrorResponse {
+                error: error.to_string(),
+            },
+        )
+    }
+}
+
+/// `None` for the missing part 2 of days with only one puzzle.
+fn solve_part(parsed_puzzle: &ParsedPuzzle, part: Part) -> Option<PartResult> {
+    let start = Instant::now();
+    let answer = parsed_puzzle.solve(part);
+    let time_us = start.elapsed().as_micros();
+    Some(match answer {
+        Ok(answer) => PartResult {
+            part: part.number(),
+            decoded: match &answer {
+                Answer::Letters { decoded, .. } => Some(decoded.clone()),
+                _ => None,
+            },
+            answer: Some(answer.to_string()),
+            time_us,
+            error: None,
+        },
+        Err(AdventError::PartNotAvailable { .. }) => return None,
+        Err(err) => PartResult {
+            part: part.number(),
+            answer: None,
+            decoded: None,
+            time_us,
+            error: Some(err.to_string()),
+        },
+    })
+}
+
+fn solve(year: &str, day: &str, puzzle_input: &str) -> Response {
+    let puzzle = match api::puzzle(year, day) {
+        Ok(puzzle) => puzzle,
+        Err(err) => return Response::error("404 Not Found", err),
+    };
+
+    let start = Instant::now();
+    let parsed_puzzle = match puzzle.parse(puzzle_input) {
+        Ok(parsed_puzzle) => parsed_puzzle,
+        Err(err) => return Response::error("422 Unprocessable Entity", err),
+    };
+    let parse_time_us = start.elapsed().as_micros();
+
+    Response::json(
+        "200 OK",
+        &SolveResponse {
+            year: year.to_string(),
+            day: day.to_string(),
+            parse_time_us,
+            parts: Part::ALL
+                .into_iter()
+                .filter_map(|part| solve_part(&parsed_puzzle, part))
+                .collect(),
+        },
+    )
+}
+
+fn route(method: &str, path: &str, body: &str) -> Response {
+    let segments = path
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    match (method, segments.as_slice()) {
+        ("GET", ["puzzles"]) => Response::json(
+            "200 OK",
+            &api::puzzles()
+                .iter()
+                .map(|puzzle| PuzzleListing {
+                    year: puzzle.year(),
+                    day: puzzle.day(),
+                })
+                .collect::<Vec<_>>(),
+        ),
+        ("POST", ["puzzles", year, day]) => solve(year, day, body),
+        (_, ["puzzles"]) | (_, ["puzzles", _, _]) => {
+            Response::error("405 Method Not Allowed", "Method not allowed for this path")
+        }
+        _ => Response::error("404 Not Found", "No endpoint at this path"),
+    }
+}
+
+fn write_response(stream: &TcpStream, response: &Response) -> Result<(), Box<dyn Error>> {
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Runs `task` on its own thread, giving up on it after `timeout`.
+fn run_with_timeout<T: Send + 'static>(
+    timeout: Duration,
+    task: impl FnOnce() -> T + Send + 'static,
+) -> Option<T> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // The receiver is gone when the task ran over, and then the result is not needed.
+        let _ = sender.send(task());
+    });
+    receiver.recv_timeout(timeout).ok()
+}
+
+fn route_with_timeout(method: String, path: String, body: String, timeout: Duration) -> Response {
+    run_with_timeout(timeout, move || route(&method, &path, &body)).unwrap_or_else(|| {
+        Response::error(
+            "504 Gateway Timeout",
+            format!("Solving took longer than {timeout:?}"),
+        )
+    })
+}
+
+fn handle_connection(stream: TcpStream) -> Result<(), Box<dyn Error>> {
+    stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut head = (&mut reader).take(MAX_HEADER_BYTES as u64);
+    let head_too_large = Response::error(
+        "431 Request Header Fields Too Large",
+        format!("Request line and headers must be at most {MAX_HEADER_BYTES} bytes"),
+    );
+
+    let mut request_line = String::new();
+    head.read_line(&mut request_line)?;
+    if !request_line.ends_with('\n') && head.limit() == 0 {
+        return write_response(&stream, &head_too_large);
+    }
+    let mut request_line_parts = request_line.split_whitespace();
+    let method = request_line_parts
+        .next()
+        .ok_or("Empty request")?
+        .to_string();
+    let path = request_line_parts
+        .next()
+        .ok_or("Request did not contain a path")?
+        .to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        head.read_line(&mut header)?;
+        if !header.ends_with('\n') && head.limit() == 0 {
+            return write_response(&stream, &head_too_large);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse::<usize>()?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        let response = Response::error(
+            "413 Payload Too Large",
+            format!("Request body must be at most {MAX_BODY_BYTES} bytes"),
+        );
+        return write_response(&stream, &response);
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let response = match String::from_utf8(body) {
+        Ok(body) => route_with_timeout(method, path, body, SOLVE_TIMEOUT),
+        Err(_) => Response::error("400 Bad Request", "Request body must be UTF-8"),
+    };
+    write_response(&stream, &response)
+}
+
+/// Starts the threads handling connections, which take them from the returned queue.
+/// The queue holds as many connections as there are workers, so accepting blocks once
+/// every worker is busy and the queue is full.
+fn start_workers() -> mpsc::SyncSender<TcpStream> {
+    let (sender, receiver) = mpsc::sync_channel::<TcpStream>(WORKER_COUNT);
+    let receiver = Arc::new(Mutex::new(receiver));
+    for _ in 0..WORKER_COUNT {
+        let receiver = Arc::clone(&receiver);
+        thread::spawn(move || loop {
+            let stream = match receiver.lock() {
+                Ok(receiver) => receiver.recv(),
+                Err(_) => return,
+            };
+            let Ok(stream) = stream else {
+                return;
+            };
+            if let Err(err) = handle_connection(stream) {
+                tracing::warn!("Failed to handle request: {err}");
+            }
+        });
+    }
+    sender
+}
+
+pub fn serve(serve_params: &ServeParams) -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind(&serve_params.address)?;
+    println!("Listening on http://{}", listener.local_addr()?);
+
+    let workers = start_workers();
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                tracing::warn!("Failed to accept connection: {err}");
+                continue;
+            }
+        };
+        workers.send(stream)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "y2023")]
+    fn route_lists_puzzles() {
+        let response = route("GET", "/puzzles", "");
+
+        assert_eq!("200 OK", response.status);
+        assert!(response.body.contains("\"year\": \"2023\""));
+    }
+
+    #[test]
+    #[cfg(feature = "y2023")]
+    fn route_solves_posted_input() {
+        let response = route(
+            "POST",
+            "/puzzles/2023/day9",
+            "0 3 6 9 12 15\n1 3 6 10 15 21",
+        );
+        let body: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+
+        assert_eq!("200 OK", response.status);
+        assert_eq!("46", body["parts"][0]["answer"]);
+        assert_eq!("-3", body["parts"][1]["answer"]);
+    }
+
+    #[test]
+    fn oversized_request_heads_are_refused() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+
+        // Exactly the limit, all of it read by the server, so closing does not reset the
+        // connection before the response arrives.
+        write!(client, "GET /{}", "a".repeat(MAX_HEADER_BYTES - 5)).unwrap();
+        handle_connection(stream).unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 431 Request Header Fields Too Large"));
+    }
+
+    #[test]
+    fn slow_solves_time_out() {
+        let expected = None;
+
+        let actual = run_with_timeout(Duration::from_millis(10), || {
+            thread::sleep(Duration::from_secs(1));
+        });
+
+        assert_eq!(expected, actual);
+        assert_eq!(Some(5), run_with_timeout(Duration::from_secs(5), || 5));
+    }
+
+    #[test]
+    fn oversized_bodies_are_refused_unread() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+
+        write!(
+            client,
+            "POST /puzzles/2023/day9 HTTP/1.1\r\nContent-Length: 99999999999999\r\n\r\n"
+        )
+        .unwrap();
+        handle_connection(stream).unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 413 Payload Too Large"));
+    }
+
+    #[test]
+    fn route_reports_unknown_puzzle() {
+        let response = route("POST", "/puzzles/2015/day1", "");
+
+        assert_eq!("404 Not Found", response.status);
+    }
+}
@@ -0,0 +1,384 @@
+use std::{error::Error, fs};
+
+const PUZZLE_PAGES_DIRECTORY: &str = "puzzles";
+const LINE_WIDTH: usize = 80;
+const PREFORMATTED_INDENT: &str = "    ";
+const LIST_ITEM_PREFIX: &str = "  - ";
+const LIST_ITEM_INDENT: &str = "    ";
+const HEADING_STYLE: &str = "\x1b[1m";
+const EMPHASIS_STYLE: &str = "\x1b[1;33m";
+const CODE_STYLE: &str = "\x1b[36m";
+const RESET_STYLE: &str = "\x1b[0m";
+const PART_2_LOCKED_MESSAGE: &str =
+    "Part 2 is still locked. Save the page again after solving part 1 to read it.";
+
+pub struct ShowParams {
+    pub year_of_puzzle: String,
+    pub day_to_run: String,
+    pub page_path: Option<String>,
+}
+
+impl ShowParams {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<ShowParams, &'static str> {
+        let year_of_puzzle = args.next().ok_or("Year of puzzle not provided!")?;
+        let day_to_run = args.next().ok_or("Day to run not provided!")?;
+
+        let mut page_path = None;
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "--page" => {
+                    page_path = Some(
+                        args.next()
+                            .ok_or("Path to puzzle page not provided after --page!")?,
+                    );
+                }
+                _ => return Err("Unrecognised flag provided!"),
+            }
+        }
+
+        Ok(ShowParams {
+            year_of_puzzle,
+            day_to_run,
+            page_path,
+        })
+    }
+}
+
+/// Where a saved puzzle page is expected, e.g. `puzzles/2023/day9.html`.
+pub fn puzzle_page_path(year_of_puzzle: &str, day_to_run: &str) -> String {
+    format!("{PUZZLE_PAGES_DIRECTORY}/{year_of_puzzle}/{day_to_run}.html")
+}
+
+pub enum HtmlToken<'a> {
+    Open(String),
+    Close(String),
+    Text(&'a str),
+}
+
+pub fn tokenize_html(page: &str) -> Vec<HtmlToken<'_>> {
+    let mut tokens = vec![];
+    let mut rest = page;
+    while !rest.is_empty() {
+        if let Some(tag) = rest.strip_prefix('<') {
+            let tag_end = tag.find('>').unwrap_or(tag.len());
+            let (closing, tag_body) = match tag[..tag_end].strip_prefix('/') {
+                Some(tag_body) => (true, tag_body),
+                None => (false, &tag[..tag_end]),
+            };
+            let name = tag_body
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or("")
+                .to_ascii_lowercase();
+            tokens.push(if closing {
+                HtmlToken::Close(name)
+            } else {
+                HtmlToken::Open(name)
+            });
+            rest = tag.get(tag_end + 1..).unwrap_or("");
+        } else {
+            let text_end = rest.find('<').unwrap_or(rest.len());
+            tokens.push(HtmlToken::Text(&rest[..text_end]));
+            rest = &rest[text_end..];
+        }
+    }
+    tokens
+}
+
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+    while let Some(entity_start) = rest.find('&') {
+        decoded.push_str(&rest[..entity_start]);
+        rest = &rest[entity_start..];
+        let entity = rest
+            .find(';')
+            .map(|entity_end| &rest[1..entity_end])
+            .and_then(|entity| {
+                let character = match entity {
+                    "lt" => '<',
+                    "gt" => '>',
+                    "amp" => '&',
+                    "quot" => '"',
+                    "apos" => '\'',
+                    "nbsp" => ' ',
+                    _ => entity
+                        .strip_prefix('#')
+                        .and_then(|code| code.parse().ok())
+                        .and_then(char::from_u32)?,
+                };
+                Some((entity.len() + 2, character))
+            });
+        match entity {
+            Some((entity_length, character)) => {
+                decoded.push(character);
+                rest = &rest[entity_length..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Turns the markup inside puzzle `<article>`s into wrapped terminal lines, keeping
+/// emphasis and code as ANSI colours.
+#[derive(Default)]
+struct Renderer {
+    lines: Vec<String>,
+    words: Vec<(String, usize)>,
+    word: String,
+    word_width: usize,
+    word_style: Option<&'static str>,
+    emphasis_depth: usize,
+    code_depth: usize,
+    in_heading: bool,
+    in_list_item: bool,
+    preformatted: bool,
+}
+
+impl Renderer {
+    fn current_style(&self) -> Option<&'static str> {
+        if self.emphasis_depth > 0 {
+            Some(EMPHASIS_STYLE)
+        } else if self.code_depth > 0 {
+            Some(CODE_STYLE)
+        } else if self.in_heading {
+            Some(HEADING_STYLE)
+        } else {
+            None
+        }
+    }
+
+    fn finish_word(&mut self) {
+        if self.word_style.take().is_some() {
+            self.word.push_str(RESET_STYLE);
+        }
+        if self.word_width > 0 {
+            self.words
+                .push((std::mem::take(&mut self.word), self.word_width));
+        }
+        self.word.clear();
+        self.word_width = 0;
+    }
+
+    fn push_character(&mut self, character: char) {
+        let style = self.current_style();
+        if style != self.word_style {
+            if self.word_style.is_some() {
+                self.word.push_str(RESET_STYLE);
+            }
+            if let Some(style) = style {
+                self.word.push_str(style);
+            }
+            self.word_style = style;
+        }
+        self.word.push(character);
+        self.word_width += 1;
+    }
+
+    fn push_text(&mut self, text: &str) {
+        for character in decode_entities(text).chars() {
+            match character {
+                '\n' if self.preformatted => self.finish_preformatted_line(),
+                _ if character.is_whitespace() && !self.preformatted => self.finish_word(),
+                _ => self.push_character(character),
+            }
+        }
+    }
+
+    fn finish_preformatted_line(&mut self) {
+        self.finish_word();
+        let line = self
+            .words
+            .drain(..)
+            .map(|(word, _)| word)
+            .collect::<String>();
+        self.lines.push(format!("{PREFORMATTED_INDENT}{line}"));
+    }
+
+    fn finish_paragraph(&mut self) {
+        self.finish_word();
+        if self.words.is_empty() {
+            return;
+        }
+        let (first_prefix, prefix) = if self.in_list_item {
+            (LIST_ITEM_PREFIX, LIST_ITEM_INDENT)
+        } else {
+            ("", "")
+        };
+        let mut line = String::from(first_prefix);
+        let mut line_width = first_prefix.len();
+        let mut line_has_words = false;
+        for (word, word_width) in self.words.drain(..) {
+            if line_has_words && line_width + 1 + word_width > LINE_WIDTH {
+                self.lines.push(line);
+                line = String::from(prefix);
+                line_width = prefix.len();
+                line_has_words = false;
+            }
+            if line_has_words {
+                line.push(' ');
+                line_width += 1;
+            }
+            line.push_str(&word);
+            line_width += word_width;
+            line_has_words = true;
+        }
+        self.lines.push(line);
+    }
+
+    fn push_blank_line(&mut self) {
+        if self.lines.last().is_some_and(|line| !line.is_empty()) {
+            self.lines.push(String::new());
+        }
+    }
+
+    fn open(&mut self, tag: &str) {
+        match tag {
+            "em" => self.emphasis_depth += 1,
+            "code" => self.code_depth += 1,
+            "h2" => {
+                self.finish_paragraph();
+                self.in_heading = true;
+            }
+            "li" => {
+                self.finish_paragraph();
+                self.in_list_item = true;
+            }
+            "pre" => {
+                self.finish_paragraph();
+                self.preformatted = true;
+            }
+            "p" | "ul" | "ol" | "br" => self.finish_paragraph(),
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, tag: &str) {
+        match tag {
+            "em" => self.emphasis_depth = self.emphasis_depth.saturating_sub(1),
+            "code" => self.code_depth = self.code_depth.saturating_sub(1),
+            "h2" => {
+                self.finish_paragraph();
+                self.in_heading = false;
+                self.push_blank_line();
+            }
+            "li" => {
+                self.finish_paragraph();
+                self.in_list_item = false;
+            }
+            "pre" => {
+                self.finish_word();
+                if !self.words.is_empty() {
+                    self.finish_preformatted_line();
+                }
+                self.preformatted = false;
+                self.push_blank_line();
+            }
+            "p" | "ul" | "ol" | "article" => {
+                self.finish_paragraph();
+                self.push_blank_line();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Renders the puzzle descriptions of a saved Advent of Code page as terminal text.
+fn render_puzzle_page(page: &str) -> Result<String, Box<dyn Error>> {
+    let mut renderer = Renderer::default();
+    let mut article_depth = 0;
+    let mut article_count = 0;
+    for token in tokenize_html(page) {
+        match token {
+            HtmlToken::Open(tag) if tag == "article" => {
+                article_depth += 1;
+                article_count += 1;
+            }
+            HtmlToken::Close(tag) if tag == "article" && article_depth > 0 => {
+                renderer.close(&tag);
+                article_depth -= 1;
+            }
+            _ if article_depth == 0 => {}
+            HtmlToken::Open(tag) => renderer.open(&tag),
+            HtmlToken::Close(tag) => renderer.close(&tag),
+            HtmlToken::Text(text) => renderer.push_text(text),
+        }
+    }
+    renderer.finish_paragraph();
+
+    if article_count == 0 {
+        return Err("No puzzle description found in the page".into());
+    }
+    if article_count == 1 {
+        renderer.lines.push(String::from(PART_2_LOCKED_MESSAGE));
+    }
+    while renderer.lines.last().is_some_and(String::is_empty) {
+        renderer.lines.pop();
+    }
+    Ok(renderer.lines.join("\n"))
+}
+
+pub fn show_puzzle(show_params: &ShowParams) -> Result<(), Box<dyn Error>> {
+    let page_path = show_params
+        .page_path
+        .clone()
+        .unwrap_or_else(|| puzzle_page_path(&show_params.year_of_puzzle, &show_params.day_to_run));
+    let page = fs::read_to_string(&page_path)
+        .map_err(|err| format!("Failed to read saved puzzle page {page_path}: {err}"))?;
+    println!("{}", render_puzzle_page(&page)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_keeps_emphasis_code_and_lists() -> Result<(), Box<dyn Error>> {
+        let expected = "\
+\x1b[1m---\x1b[0m \x1b[1mDay\x1b[0m \x1b[1m9\x1b[0m \x1b[1m---\x1b[0m
+
+Find the \x1b[1;33mnext\x1b[0m value &amp; print it.
+
+    \x1b[36m0 3 6\x1b[0m
+    \x1b[36m1 \x1b[0m\x1b[1;33m3\x1b[0m
+
+  - one \x1b[36mcode\x1b[0m.
+  - two
+
+Part 2 is still locked. Save the page again after solving part 1 to read it.";
+
+        let input = "<html><body><main><article class=\"day-desc\"><h2>--- Day 9 ---</h2>\
+<p>Find the <em>next</em> value &amp;amp; print it.</p>\
+<pre><code>0 3 6\n1 <em>3</em>\n</code></pre>\
+<ul><li>one <code>code</code>.</li><li>two</li></ul></article></main></body></html>";
+        let actual = render_puzzle_page(input)?;
+
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+
+    #[test]
+    fn render_wraps_long_paragraphs() -> Result<(), Box<dyn Error>> {
+        let input = format!(
+            "<article><p>{}</p></article><article><p>Part two.</p></article>",
+            "word ".repeat(40)
+        );
+
+        let actual = render_puzzle_page(&input)?;
+
+        assert!(actual.lines().all(|line| line.len() <= LINE_WIDTH));
+        assert!(actual.ends_with("\n\nPart two."));
+        Ok(())
+    }
+
+    #[test]
+    fn page_without_articles_is_an_error() {
+        assert!(render_puzzle_page("<html><p>Please log in</p></html>").is_err());
+    }
+}
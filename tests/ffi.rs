@@ -0,0 +1,71 @@
+//! Checks the C API from the outside: the checked-in header against the one generated by
+//! the build script, and `tests/ffi/solve.c` against the `cdylib`.
+#![cfg(feature = "ffi")]
+
+use std::{env, fs, path::PathBuf};
+
+const GENERATED_HEADER: &str = include_str!(concat!(env!("OUT_DIR"), "/rust_advent.h"));
+
+/// Set `ADVENT_UPDATE_HEADER=1` to overwrite `include/rust_advent.h` with the generated
+/// header instead of failing.
+#[test]
+fn checked_in_header_is_up_to_date() {
+    let header_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("include/rust_advent.h");
+    if env::var_os("ADVENT_UPDATE_HEADER").is_some() {
+        fs::write(&header_path, GENERATED_HEADER).expect("Header could not be written");
+    }
+    let checked_in_header = fs::read_to_string(&header_path).expect("Header could not be read");
+
+    assert!(
+        checked_in_header == GENERATED_HEADER,
+        "include/rust_advent.h is out of date, run ADVENT_UPDATE_HEADER=1 cargo test --features ffi"
+    );
+}
+
+#[test]
+#[cfg(all(feature = "y2023", unix))]
+fn c_program_solves_through_the_c_api() {
+    use std::process::Command;
+
+    let manifest_directory = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // The library's cdylib is built into `target/<profile>/deps` along with the rlib the
+    // tests link against, which is where the tests themselves run from.
+    let test_executable = env::current_exe().expect("Test executable could not be found");
+    let library_directory = test_executable
+        .parent()
+        .expect("Test executable is not in a target directory")
+        .to_path_buf();
+    let compiler = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    let program = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("ffi_solve");
+
+    let compile = Command::new(&compiler)
+        .arg(manifest_directory.join("tests/ffi/solve.c"))
+        .arg("-I")
+        .arg(manifest_directory.join("include"))
+        .arg("-L")
+        .arg(&library_directory)
+        .arg("-lrust_advent")
+        .arg(format!("-Wl,-rpath,{}", library_directory.display()))
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-o")
+        .arg(&program)
+        .output()
+        .expect("C compiler could not be run, set CC to choose one");
+    assert!(
+        compile.status.success(),
+        "{}",
+        String::from_utf8_lossy(&compile.stderr)
+    );
+
+    let run = Command::new(&program)
+        .env("LD_LIBRARY_PATH", &library_directory)
+        .env("DYLD_LIBRARY_PATH", &library_directory)
+        .output()
+        .expect("Compiled C program could not be run");
+    assert!(
+        run.status.success(),
+        "{}",
+        String::from_utf8_lossy(&run.stderr)
+    );
+}
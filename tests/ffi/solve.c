@@ -0,0 +1,49 @@
+/* Solves a day through the C API and checks the answers, exiting non-zero on failure. */
+#include <stdio.h>
+#include <string.h>
+
+#include "rust_advent.h"
+
+static int expect(const char *name, const char *actual, const char *expected) {
+  if (actual == NULL || strcmp(actual, expected) != 0) {
+    fprintf(stderr, "%s: expected %s, got %s\n", name, expected,
+            actual == NULL ? "NULL" : actual);
+    return 1;
+  }
+  return 0;
+}
+
+int main(void) {
+  int failures = 0;
+
+  char *puzzles = advent_list_puzzles();
+  if (puzzles == NULL || strstr(puzzles, "2023 day9") == NULL) {
+    fprintf(stderr, "2023 day9 missing from the puzzle list\n");
+    failures++;
+  }
+  advent_free_string(puzzles);
+
+  /* Not NUL-terminated, to check the length is respected. */
+  const char input[] = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45garbage";
+  AdventAnswers answers;
+  AdventStatus status = advent_solve("2023", "day9", (const uint8_t *)input,
+                                     strlen(input) - strlen("garbage"), &answers);
+  if (status != ADVENT_STATUS_OK) {
+    fprintf(stderr, "solve failed with %d: %s\n", status, answers.error);
+    failures++;
+  }
+  failures += expect("first", answers.first, "114");
+  failures += expect("second", answers.second, "2");
+  advent_free_answers(&answers);
+  advent_free_answers(&answers);
+
+  status = advent_solve("2015", "day1", NULL, 0, &answers);
+  if (status != ADVENT_STATUS_YEAR_NOT_IMPLEMENTED || answers.first != NULL) {
+    fprintf(stderr, "expected 2015 to be reported as not implemented\n");
+    failures++;
+  }
+  failures += expect("error", answers.error, "Year 2015 has not been implemented yet");
+  advent_free_answers(&answers);
+
+  return failures == 0 ? 0 : 1;
+}